@@ -0,0 +1,195 @@
+//! Typed puzzle answers; integers, text and raster images
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeStruct;
+
+use crate::error::AocError;
+use crate::utils::Grid;
+
+/// lit pixel of a raster image
+const LIT: char = '#';
+/// dark pixel of a raster image
+const DARK: char = '.';
+
+/// An answer to a puzzle
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Answer {
+    /// a number, i.e. a count or a sum
+    Integer(i128),
+    /// text, i.e. the day 5 crates on top of each stack
+    Text(String),
+    /// a raster image, i.e. the day 10 crt frame
+    Image(Raster),
+}
+
+/// displays an answer on one line, so every answer fits a line of the results;
+/// the alternate format, `{:#}`, displays an image row by row
+impl Display for Answer {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => write!(fmt, "{value}"),
+            Self::Text(value) => write!(fmt, "{value}"),
+            Self::Image(image) if fmt.alternate() => write!(fmt, "{image}"),
+            Self::Image(image) => write!(fmt, "{}", image.rows().collect::<String>()),
+        }
+    }
+}
+
+macro_rules! integer_answers {
+    ($($integer:ty),*) => {
+        $(
+            impl From<$integer> for Answer {
+                #[allow(clippy::cast_lossless, clippy::cast_possible_wrap)] // `usize` and `isize` lack `From` for `i128`
+                fn from(value: $integer) -> Self {
+                    Self::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_answers!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// a `u128` total of a stress input, an overflow if it doesn't fit an integer answer
+impl TryFrom<u128> for Answer {
+    type Error = AocError;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        i128::try_from(value)
+            .map(Self::Integer)
+            .map_err(|_| AocError::overflow(format!("an integer answer, {value} is more than {}", i128::MAX)))
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<Raster> for Answer {
+    fn from(image: Raster) -> Self {
+        Self::Image(image)
+    }
+}
+
+/// A monochrome raster image of lit and dark pixels
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Raster {
+    pixels: Grid<bool>,
+}
+
+impl Raster {
+    /// a dark raster image of a width and height
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            pixels: Grid::new(width, height),
+        }
+    }
+
+    /// width of the image in pixels
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.pixels.width()
+    }
+
+    /// height of the image in pixels
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.pixels.height()
+    }
+
+    /// is a pixel lit, `None` if it is outside the image
+    #[must_use]
+    pub fn lit(&self, x: usize, y: usize) -> Option<bool> {
+        self.pixels.get(x, y).copied()
+    }
+
+    /// lights or darkens a pixel, pixels outside the image are ignored
+    pub fn set(&mut self, x: usize, y: usize, lit: bool) {
+        self.pixels.set(x, y, lit);
+    }
+
+    /// rows of the image, top to bottom, as `#` lit and `.` dark pixels
+    pub fn rows(&self) -> impl Iterator<Item=String> + '_ {
+        self.pixels.rows()
+            .map(|row| row.iter().map(|lit| if *lit { LIT } else { DARK }).collect())
+    }
+}
+
+/// an image of the lit pixels of a grid
+impl From<Grid<bool>> for Raster {
+    fn from(pixels: Grid<bool>) -> Self {
+        Self { pixels }
+    }
+}
+
+/// displays the image row by row
+impl Display for Raster {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", self.rows().collect::<Vec<_>>().join("\n"))
+    }
+}
+
+/// serializes the image's size and its rows of `#` lit and `.` dark pixels
+impl Serialize for Raster {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut image = serializer.serialize_struct("Raster", 3)?;
+
+        image.serialize_field("width", &self.width())?;
+        image.serialize_field("height", &self.height())?;
+        image.serialize_field("rows", &self.rows().collect::<Vec<_>>())?;
+
+        image.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Answer, Raster};
+
+    #[test]
+    fn display_answers() {
+        let mut image = Raster::new(3, 2);
+
+        image.set(0, 0, true);
+        image.set(2, 1, true);
+        image.set(3, 0, true);
+
+        assert_eq!(Answer::from(42_usize).to_string(), "42");
+        assert_eq!(Answer::from(-7_isize).to_string(), "-7");
+        assert_eq!(Answer::try_from(u128::from(u64::MAX) + 1).ok(), Some(Answer::Integer(1 << 64)));
+        assert!(Answer::try_from(u128::MAX).is_err());
+        assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+        assert_eq!(Answer::from(image.clone()).to_string(), "#....#");
+        assert_eq!(format!("{:#}", Answer::from(image.clone())), "#..\n..#");
+        assert_eq!(image.lit(2, 1), Some(true));
+        assert_eq!(image.lit(3, 0), None);
+    }
+
+    #[test]
+    fn serialize_answers() {
+        let mut image = Raster::new(2, 2);
+
+        image.set(1, 0, true);
+
+        let serialized = |answer: Answer| serde_json::to_string(&answer).expect("serializable answer");
+
+        assert_eq!(serialized(Answer::from(13_954_061_248_usize)), r#"{"integer":13954061248}"#);
+        assert_eq!(serialized(Answer::from("CMZ")), r#"{"text":"CMZ"}"#);
+        assert_eq!(serialized(Answer::from(image)), r#"{"image":{"width":2,"height":2,"rows":[".#",".."]}}"#);
+    }
+}
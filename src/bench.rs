@@ -0,0 +1,69 @@
+//! Timing summaries of benchmarked puzzles
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// Summary of the solve times of repeated runs of a puzzle
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Summary {
+    /// fastest run
+    pub min: Duration,
+    /// median run, the mean of the two middle runs for an even number of runs
+    pub median: Duration,
+    /// slowest run
+    pub max: Duration,
+    /// number of runs
+    pub runs: usize,
+}
+
+impl Summary {
+    /// summarize timings of runs, `None` if there are no timings
+    #[must_use]
+    pub fn new(mut timings: Vec<Duration>) -> Option<Self> {
+        timings.sort_unstable();
+
+        let runs = timings.len();
+        let min = *timings.first()?;
+        let max = *timings.last()?;
+        let middle = runs >> 1;
+
+        let median = if runs & 1 == 0 {
+            (timings[middle - 1] + timings[middle]) / 2
+        } else {
+            timings[middle]
+        };
+
+        Some(Self { min, median, max, runs })
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt, "min {:?}, median {:?}, max {:?} ({} runs)",
+            self.min, self.median, self.max, self.runs
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Summary;
+
+    #[test]
+    fn summarize_timings() {
+        let millis = |ms: &[u64]| ms.iter().copied().map(Duration::from_millis).collect::<Vec<_>>();
+
+        let odd = Summary::new(millis(&[5, 1, 3])).expect("a summary");
+
+        assert_eq!((odd.min, odd.median, odd.max), (Duration::from_millis(1), Duration::from_millis(3), Duration::from_millis(5)));
+
+        let even = Summary::new(millis(&[4, 1, 8, 2])).expect("a summary");
+
+        assert_eq!(even.median, Duration::from_millis(3));
+        assert!(Summary::new(Vec::new()).is_none());
+    }
+}
@@ -0,0 +1,314 @@
+//! Command line options of the puzzle runner
+
+use std::num::NonZeroUsize;
+
+use crate::input::InputSource;
+use crate::report::Format;
+use crate::selection::Selection;
+use crate::solution::{Mode, Solution, SOLUTIONS};
+
+/// usage of the puzzle runner
+pub const USAGE: &str = "usage: aoc-2022 [--time] [--bench N] [--format FORMAT] [--input PATH] [--jobs N] [--lenient] [SELECTION]...
+       aoc-2022 --example [--jobs N] [SELECTION]...
+       aoc-2022 verify [--input PATH] [--jobs N] [--lenient] [SELECTION]...
+       aoc-2022 new DAY [TITLE]
+       aoc-2022 top K [--input PATH]
+       aoc-2022 stats [--input PATH]
+       aoc-2022 decode [--input PATH]
+
+verify           solve puzzles and compare answers with the expected results
+new              generate, register and create an empty input file for a new day's puzzle
+top              list the K elves of day 1 carrying the most calories, with their totals
+stats            report statistics of the calories the elves of day 1 carry, flagging suspicious records
+decode           score the day 2 strategy guide under every decoding, showing the rounds of the best
+SELECTION        a day `7`, a day's part `9:2` or a range of days `3..=6`, `3..7`
+--example        solve the worked examples of the puzzle text, showing expected and actual answers
+--time           report read, parse, and parse and solve times of each puzzle
+--bench N        solve each puzzle N times and report min/median/max solve times
+--format FORMAT  format of the results file; text, json or csv
+--input PATH     input file of a single selected day, `-` for stdin
+--jobs N         solve puzzles on N threads, results keep their order
+--lenient        skip invalid lines of input, of days that can, reporting how many were skipped
+
+AOC_INPUT_DIR    folder of puzzle input files, defaults to `puzzle_input`";
+
+/// Commands of the puzzle runner
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum Command {
+    /// solve puzzles and write their results
+    #[default]
+    Run,
+    /// solve the worked examples of puzzles
+    Examples,
+    /// solve puzzles and compare their answers with the expected results
+    Verify,
+    /// scaffold a new day's puzzle
+    New {
+        /// day of the advent calendar to scaffold
+        day: u8,
+        /// title of the day's puzzle
+        title: String,
+    },
+    /// list the elves carrying the most calories
+    Top {
+        /// number of elves to list
+        k: NonZeroUsize,
+    },
+    /// report statistics of the calories the elves carry
+    Stats,
+    /// score every decoding of the strategy guide
+    Decode,
+}
+
+/// Options the puzzle runner was started with
+#[derive(Debug)]
+pub struct Options {
+    /// command to run
+    pub command: Command,
+    /// puzzles selected to run, all puzzles if empty
+    pub selections: Vec<Selection>,
+    /// report timings of each puzzle
+    pub time: bool,
+    /// number of times to solve each puzzle for a benchmark
+    pub bench: Option<NonZeroUsize>,
+    /// format of the results file
+    pub format: Format,
+    /// where puzzle input is read from
+    pub input: InputSource,
+    /// number of threads to solve puzzles on
+    pub jobs: NonZeroUsize,
+    /// strict or lenient about invalid lines of input
+    pub mode: Mode,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            command: Command::default(),
+            selections: Vec::new(),
+            time: false,
+            bench: None,
+            format: Format::default(),
+            input: InputSource::default(),
+            jobs: NonZeroUsize::MIN,
+            mode: Mode::default(),
+        }
+    }
+}
+
+impl Options {
+    /// parse options from command line arguments, excluding the program name
+    ///
+    /// # Errors
+    ///
+    /// fails with a description of the first invalid argument
+    pub fn parse(args: impl IntoIterator<Item=String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter().peekable();
+
+        if args.next_if(|arg| arg == "verify").is_some() {
+            options.command = Command::Verify;
+        } else if args.next_if(|arg| arg == "new").is_some() {
+            let day = args.next().ok_or_else(|| format!("new expects a day\n\n{USAGE}"))?;
+            let day = day.parse::<u8>()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("'{day}' is not a valid day, expected 1 to 25"))?;
+
+            let title = args.next().unwrap_or_else(|| String::from("TODO"));
+
+            if let Some(unexpected) = args.next() {
+                return Err(format!("unexpected argument '{unexpected}' for new\n\n{USAGE}"));
+            }
+
+            options.command = Command::New { day, title };
+
+            return Ok(options);
+        } else if args.next_if(|arg| arg == "top").is_some() {
+            let k = args.next().ok_or_else(|| format!("top expects a number of elves\n\n{USAGE}"))?;
+            let k = k.parse()
+                .map_err(|err| format!("'{k}' is not a valid number of elves; {err}"))?;
+
+            options.command = Command::Top { k };
+            options.input = parse_input_only(args, "top")?;
+
+            return Ok(options);
+        } else if args.next_if(|arg| arg == "stats").is_some() {
+            options.command = Command::Stats;
+            options.input = parse_input_only(args, "stats")?;
+
+            return Ok(options);
+        } else if args.next_if(|arg| arg == "decode").is_some() {
+            options.command = Command::Decode;
+            options.input = parse_input_only(args, "decode")?;
+
+            return Ok(options);
+        }
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--time" => options.time = true,
+                "--lenient" => options.mode = Mode::Lenient,
+                "--example" | "--examples" if options.command == Command::Run => options.command = Command::Examples,
+                "--bench" => {
+                    let runs = args.next().ok_or_else(|| format!("--bench expects a number of runs\n\n{USAGE}"))?;
+
+                    options.bench = Some(
+                        runs.parse()
+                            .map_err(|err| format!("'{runs}' is not a valid number of --bench runs; {err}"))?
+                    );
+                }
+                "--format" => {
+                    let format = args.next().ok_or_else(|| format!("--format expects a format\n\n{USAGE}"))?;
+
+                    options.format = format.parse()?;
+                }
+                "--input" => {
+                    let input = args.next().ok_or_else(|| format!("--input expects a path, or `-` for stdin\n\n{USAGE}"))?;
+
+                    options.input = input.parse()?;
+                }
+                "--jobs" | "-j" => {
+                    let jobs = args.next().ok_or_else(|| format!("--jobs expects a number of threads\n\n{USAGE}"))?;
+
+                    options.jobs = jobs.parse()
+                        .map_err(|err| format!("'{jobs}' is not a valid number of --jobs; {err}"))?;
+                }
+                "--help" | "-h" => return Err(USAGE.to_string()),
+                option if option.starts_with("--") =>
+                    return Err(format!("unknown option '{option}'\n\n{USAGE}")),
+                selection => {
+                    let parsed = selection.parse::<Selection>()?;
+
+                    // every selection should select at least one puzzle
+                    if !SOLUTIONS.iter().any(|solution| parsed.matches(solution.day(), solution.part())) {
+                        return Err(format!("there are no puzzle solutions for '{selection}'"));
+                    }
+
+                    options.selections.push(parsed);
+                }
+            }
+        }
+
+        // an input file or stdin is the input of only one day
+        if options.input != InputSource::PuzzleFolder {
+            let mut days = SOLUTIONS.iter()
+                .filter(|solution| options.is_selected(**solution))
+                .map(|solution| solution.day());
+
+            let day = days.next();
+
+            if days.any(|next| Some(next) != day) {
+                return Err(String::from("--input requires selecting a single day"));
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// checks if a solution is selected to run
+    pub fn is_selected(&self, solution: &dyn Solution) -> bool {
+        self.selections.is_empty() ||
+            self.selections.iter().any(|selection| selection.matches(solution.day(), solution.part()))
+    }
+}
+
+/// parses the arguments of a command of day 1, which takes only an input
+fn parse_input_only(mut args: impl Iterator<Item=String>, command: &str) -> Result<InputSource, String> {
+    let mut source = InputSource::default();
+
+    while let Some(arg) = args.next() {
+        if arg != "--input" {
+            return Err(format!("unexpected argument '{arg}' for {command}\n\n{USAGE}"));
+        }
+
+        let input = args.next().ok_or_else(|| format!("--input expects a path, or `-` for stdin\n\n{USAGE}"))?;
+
+        source = input.parse()?;
+    }
+
+    Ok(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::input::InputSource;
+    use crate::report::Format;
+    use crate::solution::Mode;
+
+    use super::{Command, Options};
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        Options::parse(args.iter().map(ToString::to_string))
+    }
+
+    #[test]
+    fn parse_options() {
+        let options = parse(&["--time", "7", "--bench", "5", "9:2", "--format", "json"]).expect("valid options");
+
+        assert_eq!(options.command, Command::Run);
+        assert!(options.time);
+        assert_eq!(options.format, Format::Json);
+        assert_eq!(options.bench.map(usize::from), Some(5));
+        assert_eq!(options.jobs.get(), 1);
+        assert_eq!(options.selections.len(), 2);
+
+        assert_eq!(options.mode, Mode::Strict);
+
+        let options = parse(&["verify", "3..=6", "--jobs", "4", "--lenient"]).expect("valid options");
+
+        assert_eq!(options.command, Command::Verify);
+        assert_eq!(options.jobs.get(), 4);
+        assert_eq!(options.mode, Mode::Lenient);
+        assert_eq!(options.selections.len(), 1);
+
+        let options = parse(&["--example", "6"]).expect("valid options");
+
+        assert_eq!(options.command, Command::Examples);
+
+        let options = parse(&["new", "12", "Hill Climbing Algorithm"]).expect("valid options");
+
+        assert_eq!(options.command, Command::New { day: 12, title: String::from("Hill Climbing Algorithm") });
+
+        let options = parse(&["top", "5", "--input", "-"]).expect("valid options");
+
+        assert_eq!(options.command, Command::Top { k: NonZeroUsize::new(5).expect("non zero") });
+        assert_eq!(options.input, InputSource::Stdin);
+        assert_eq!(parse(&["stats"]).map(|options| options.command), Ok(Command::Stats));
+        assert_eq!(parse(&["decode"]).map(|options| options.command), Ok(Command::Decode));
+
+        let options = parse(&["--input", "-", "9:2"]).expect("valid options");
+
+        assert_eq!(options.input, InputSource::Stdin);
+    }
+
+    #[test]
+    fn reject_invalid_options() {
+        let invalid_options = [
+            &["--bench"][..],
+            &["--bench", "0"],
+            &["--fast"],
+            &["24"],
+            &["--format", "xml"],
+            &["7", "verify"],
+            &["--input", "day.txt"],
+            &["--input", "day.txt", "3..=4"],
+            &["--jobs", "0"],
+            &["verify", "--example"],
+            &["new"],
+            &["new", "26"],
+            &["new", "12", "title", "7"],
+            &["top"],
+            &["top", "0"],
+            &["top", "3", "7"],
+            &["stats", "--input"],
+            &["decode", "2"],
+        ];
+
+        for invalid in invalid_options {
+            assert!(parse(invalid).is_err(), "{invalid:?} should be rejected");
+        }
+    }
+}
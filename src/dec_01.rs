@@ -1,103 +1,452 @@
 //! [AOC 2022 Day 1](https://adventofcode.com/2022/day/1)
 
-use std::io;
-use std::io::{BufRead, BufReader, Lines, Read};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::marker::PhantomData;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
-use crate::utils::io_error;
+use num_traits::{CheckedAdd, Zero};
 
-/// Iterates a file of elf calories and sums up total calories for each elf
-struct ElfCalories<R> {
-    lines: Lines<BufReader<R>>,
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{Records, TryIterator};
+use crate::utils::parse::{parse_line, unsigned};
+
+/// The snacks an elf carries, where they are listed and their total calories
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Inventory<N = usize> {
+    /// position of the elf's snacks in the input, starting at 1
+    pub elf: usize,
+    /// line number of the elf's first snack
+    pub line: usize,
+    /// number of snacks the elf carries
+    pub snacks: usize,
+    /// total calories of the snacks
+    pub calories: N,
+}
+
+/// Iterates a file of elf calories, yielding the inventory of each elf, totaling calories in `N`;
+/// `u128` totals stress inputs that overflow `usize`. Iteration ends after the first io error
+pub struct Inventories<R, N = usize> {
+    records: Records<R>,
+    elves: usize,
+    totals: PhantomData<N>,
 }
 
-impl<R> ElfCalories<R>
+impl<R, N> Inventories<R, N>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// iterate the inventory of each elf in an input
+    pub fn new(input: R) -> Self {
         Self {
-            lines: BufReader::new(input).lines()
+            records: Records::new(input),
+            elves: 0,
+            totals: PhantomData,
         }
     }
+
+    /// line number of the last line read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.records.line()
+    }
+}
+
+impl<R, N> Iterator for Inventories<R, N>
+    where R: Read,
+          N: FromStr + CheckedAdd + Zero,
+          N::Err: Display
+{
+    type Item = Result<Inventory<N>, AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the calories of each snack an elf carries are a record
+        let (start, snacks) = match self.records.next()? {
+            Ok(record) => record,
+            Err(err) => return Some(Err(err.into())),
+        };
+
+        self.elves += 1;
+
+        let elf = self.elves;
+        let calories = snacks.iter()
+            .zip(start..)
+            .map(|(snack, line)| parse_line(snack, unsigned::<N>).map_err(|err| err.in_line(DAY, line, snack)))
+            .try_checked_sum(|snack| AocError::overflow(format!("elf {elf}, day {DAY}, line {}", start + snack - 1)));
+
+        Some(calories.map(|calories| Inventory { elf, line: start, snacks: snacks.len(), calories }))
+    }
+}
+
+/// Iterates a file of elf calories and sums up total calories for each elf, in `N`;
+/// iteration ends after the first io error
+pub struct ElfCalories<R, N = usize> {
+    inventories: Inventories<R, N>,
 }
 
-impl<R> Iterator for ElfCalories<R>
+impl<R, N> ElfCalories<R, N>
     where R: Read
 {
-    type Item = io::Result<usize>;
+    /// iterate the total calories of each elf in an input
+    pub fn new(input: R) -> Self {
+        Self {
+            inventories: Inventories::new(input),
+        }
+    }
+}
+
+impl<R, N> Iterator for ElfCalories<R, N>
+    where R: Read,
+          N: FromStr + CheckedAdd + Zero,
+          N::Err: Display
+{
+    type Item = Result<N, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut total_calories = None;
-
-        loop {
-            if let Some(next) = self.lines.next() {
-                match next {
-                    // only parse and compute lines with values
-                    Ok(value) if !value.trim().is_empty() =>
-                    // parse string value
-                        match usize::from_str(&value) {
-                            // if first value initialize total calories
-                            Ok(value) if total_calories.is_none() =>
-                                total_calories = Some(value),
-                            // if existing value add to total calories
-                            Ok(value) => {
-                                total_calories = total_calories.map(|v| v + value);
-                            }
-                            // bubble usize paring errors
-                            Err(err) => return Some(Err(io_error(&format!("{value}: {err}"))))
-                        },
-                    // if empty line indicator and we have a value return
-                    Ok(_) if total_calories.is_some() =>
-                        return total_calories.map(Ok),
-                    // continue until value is found
-                    Ok(_) => {}
-                    // bubble io errors
-                    Err(err) =>
-                        return Some(Err(err)),
-                }
-            } else {
-                // no more lines return last result
-                return total_calories.map(Ok);
-            }
+        Some(self.inventories.next()?.map(|inventory| inventory.calories))
+    }
+}
+
+/// An elf and the total calories of the snacks it carries
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ElfTotal<N = usize> {
+    /// position of the elf's snacks in the input, starting at 1
+    pub elf: usize,
+    /// total calories the elf carries
+    pub calories: N,
+}
+
+/// ordered by calories; of elves carrying the same calories, the elf earlier in the input is larger
+impl<N> Ord for ElfTotal<N>
+    where N: Ord
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.calories.cmp(&other.calories).then_with(|| other.elf.cmp(&self.elf))
+    }
+}
+
+impl<N> PartialOrd for ElfTotal<N>
+    where N: Ord
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// the `k` elves carrying the most calories, most first, totaled in `N`;
+/// streams the input, holding no more than `k` elves at a time
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read, isn't valid or the calories of an elf overflow `N`
+pub fn top_elves<N, R>(input: R, k: usize) -> Result<Vec<ElfTotal<N>>, AocError>
+    where R: Read,
+          N: Ord + FromStr + CheckedAdd + Zero,
+          N::Err: Display
+{
+    ElfCalories::<R, N>::new(input)
+        .zip(1..)
+        .map(|(calories, elf)| calories.map(|calories| ElfTotal { elf, calories }))
+        .try_top_k(k)
+}
+
+/// total calories of elves, in `N`
+///
+/// # Errors
+///
+/// fails naming the elf whose calories overflow the total
+pub fn total_calories<N>(elves: &[ElfTotal<N>]) -> Result<N, AocError>
+    where N: Copy + CheckedAdd + Zero
+{
+    elves.iter()
+        .map(|elf| Ok(elf.calories))
+        .try_checked_sum(|position| AocError::overflow(format!("elf {}", elves[position - 1].elf)))
+}
+
+/// the most calories an elf carries, totaled in `N`; `u128` for stress inputs
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read, isn't valid or the calories of an elf overflow `N`
+pub fn max_calories<N, R>(input: R) -> Result<N, AocError>
+    where R: Read,
+          N: Ord + FromStr + CheckedAdd + Zero,
+          N::Err: Display
+{
+    Ok(ElfCalories::<R, N>::new(input).try_max()?.unwrap_or_else(N::zero))
+}
+
+/// Statistics of the calories the elves carry
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalorieStatistics {
+    /// number of elves
+    pub elves: usize,
+    /// number of snacks of every elf
+    pub snacks: usize,
+    /// fewest snacks an elf carries
+    pub fewest_snacks: usize,
+    /// most snacks an elf carries
+    pub most_snacks: usize,
+    /// mean number of snacks an elf carries
+    pub mean_snacks: f64,
+    /// median number of snacks an elf carries
+    pub median_snacks: f64,
+    /// mean total calories of an elf
+    pub mean: f64,
+    /// median total calories of an elf
+    pub median: f64,
+    /// population standard deviation of the total calories of an elf
+    pub std_dev: f64,
+    /// total calories at each of the [`PERCENTILES`], nearest rank
+    pub percentiles: Vec<(u8, u128)>,
+    /// number of elves by their total calories, in equally wide ranges of calories
+    pub histogram: Vec<(RangeInclusive<u128>, usize)>,
+    /// records that may not be what the elves meant, in input order
+    pub suspicious: Vec<Suspicious>,
+}
+
+/// percentiles of the total calories of the statistics
+pub const PERCENTILES: [u8; 6] = [10, 25, 50, 75, 90, 99];
+
+/// number of ranges of calories of the histogram
+const HISTOGRAM_BINS: u128 = 10;
+
+/// width of the histogram bar of the most elves
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// A record of elf calories that may not be what the elves meant
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Suspicious {
+    /// more than one blank line before the snacks of an elf, as if elves carrying no snacks were in between,
+    /// or any blank line before the first or after the last elf
+    EmptyGroups {
+        /// line of the first snack after the blank lines, or of the first blank line after the last elf
+        line: usize,
+        /// number of groups of no snacks
+        groups: usize,
+    },
+    /// an elf whose snacks have no calories at all
+    NoCalories {
+        /// position of the elf
+        elf: usize,
+        /// line of the elf's first snack
+        line: usize,
+    },
+    /// an elf carrying more calories than 32 bit arithmetic can total
+    OverflowProne {
+        /// position of the elf
+        elf: usize,
+        /// line of the elf's first snack
+        line: usize,
+        /// total calories of the elf
+        calories: u128,
+    },
+}
+
+impl Display for Suspicious {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyGroups { line, groups } =>
+                write!(fmt, "line {line}: {groups} empty group(s) of snacks around it, extra blank lines"),
+            Self::NoCalories { elf, line } =>
+                write!(fmt, "line {line}: elf {elf} carries no calories"),
+            Self::OverflowProne { elf, line, calories } =>
+                write!(fmt, "line {line}: elf {elf} carries {calories} calories, more than 32 bits can total"),
         }
     }
 }
 
-// find the calories of the elf carrying the most
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// statistics of the calories the elves carry, totaled in `u128` so stress inputs don't overflow;
+/// streams the input, holding only the total of each elf
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+#[allow(clippy::cast_precision_loss)] // means, medians and deviations are approximate past the 53 bits an f64 holds exactly
+pub fn calorie_statistics<R>(input: R) -> Result<CalorieStatistics, AocError>
     where R: Read
 {
-    let elf_calorie_counter = ElfCalories::new(input);
-    let max_elf_calories = elf_calorie_counter
-        .into_iter()
-        .reduce(|acc, nxt| Ok(acc?.max(nxt?)));
+    let mut totals = Vec::new();
+    let mut carrying = Vec::new();
+    let mut snacks = 0;
+    let mut fewest_snacks = usize::MAX;
+    let mut most_snacks = 0;
+    let mut suspicious = Vec::new();
+    let mut last_line = 0;
+
+    // running mean and sum of squared differences, Welford's algorithm
+    let mut mean = 0.0;
+    let mut squares = 0.0;
+
+    let mut inventories = Inventories::<R, u128>::new(input);
+
+    for inventory in inventories.by_ref() {
+        let Inventory { elf, line, snacks: carried, calories } = inventory?;
+
+        // one blank line separates elves, none precedes the first
+        let blank_lines = line - last_line - 1;
+        let separators = usize::from(elf > 1);
 
-    match max_elf_calories {
-        None => Ok(Box::new(0)),
-        Some(Ok(value)) => Ok(Box::new(value)),
-        Some(Err(err)) => Err(err)
+        if blank_lines > separators {
+            suspicious.push(Suspicious::EmptyGroups { line, groups: blank_lines - separators });
+        }
+
+        if calories == 0 {
+            suspicious.push(Suspicious::NoCalories { elf, line });
+        }
+
+        if u32::try_from(calories).is_err() {
+            suspicious.push(Suspicious::OverflowProne { elf, line, calories });
+        }
+
+        last_line = line + carried - 1;
+        snacks += carried;
+        fewest_snacks = fewest_snacks.min(carried);
+        most_snacks = most_snacks.max(carried);
+
+        carrying.push(carried as u128);
+        totals.push(calories);
+
+        let delta = calories as f64 - mean;
+
+        mean += delta / totals.len() as f64;
+        squares += delta * (calories as f64 - mean);
+    }
+
+    // no blank line follows the last elf either
+    let trailing = inventories.line() - last_line;
+
+    if trailing > 0 {
+        suspicious.push(Suspicious::EmptyGroups { line: last_line + 1, groups: trailing });
     }
+
+    totals.sort_unstable();
+    carrying.sort_unstable();
+
+    let elves = totals.len();
+
+    let percentiles = if elves == 0 {
+        Vec::new()
+    } else {
+        PERCENTILES.iter()
+            .map(|percentile| (*percentile, totals[(usize::from(*percentile) * elves).div_ceil(100).max(1) - 1]))
+            .collect()
+    };
+
+    Ok(CalorieStatistics {
+        elves,
+        snacks,
+        fewest_snacks: fewest_snacks.min(most_snacks),
+        most_snacks,
+        mean_snacks: if elves == 0 { 0.0 } else { snacks as f64 / elves as f64 },
+        median_snacks: median(&carrying),
+        mean,
+        median: median(&totals),
+        std_dev: if elves == 0 { 0.0 } else { (squares / elves as f64).sqrt() },
+        percentiles,
+        histogram: histogram(&totals),
+        suspicious,
+    })
+}
+
+/// median of sorted values, the mean of the middle two of an even number of values
+#[allow(clippy::cast_precision_loss)] // approximate past the 53 bits an f64 holds exactly
+fn median(sorted: &[u128]) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        len if len.is_multiple_of(2) => f64::midpoint(sorted[len / 2 - 1] as f64, sorted[len / 2] as f64),
+        len => sorted[len / 2] as f64,
+    }
+}
+
+/// number of elves in equally wide ranges of calories, from the fewest to the most calories
+fn histogram(totals: &[u128]) -> Vec<(RangeInclusive<u128>, usize)> {
+    let (Some(&fewest), Some(&most)) = (totals.first(), totals.last()) else {
+        return Vec::new();
+    };
+
+    let width = (most - fewest) / HISTOGRAM_BINS + 1;
+
+    // the ranges of totals near the largest `u128` end at it
+    (0..HISTOGRAM_BINS)
+        .map_while(|bin| fewest.checked_add(bin * width))
+        .take_while(|start| *start <= most)
+        .map(|start| {
+            let calories = start..=start.saturating_add(width - 1);
+            let elves = totals.iter().filter(|total| calories.contains(total)).count();
+
+            (calories, elves)
+        })
+        .collect()
 }
 
-// find the total calories of the top three elves carrying the most
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// a text report of the statistics, with a bar of the histogram for each range of calories
+impl Display for CalorieStatistics {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(fmt, "elves: {}", self.elves)?;
+        writeln!(
+            fmt, "snacks: {}, {} to {} per elf, mean {:.1}, median {:.1}",
+            self.snacks, self.fewest_snacks, self.most_snacks, self.mean_snacks, self.median_snacks
+        )?;
+        writeln!(fmt, "calories: mean {:.1}, median {:.1}, standard deviation {:.1}", self.mean, self.median, self.std_dev)?;
+
+        let percentiles = self.percentiles.iter()
+            .map(|(percentile, calories)| format!("p{percentile} {calories}"))
+            .collect::<Vec<_>>();
+
+        writeln!(fmt, "percentiles: {}", percentiles.join(", "))?;
+
+        let most = self.histogram.iter().map(|(_, elves)| *elves).max().unwrap_or_default().max(1);
+        let digits = self.histogram.last().map_or(1, |(calories, _)| calories.end().to_string().len());
+
+        writeln!(fmt, "histogram:")?;
+
+        for (calories, elves) in &self.histogram {
+            let bar = "#".repeat(elves * HISTOGRAM_WIDTH / most);
+
+            writeln!(fmt, "  {:>digits$} - {:>digits$} | {bar} {elves}", calories.start(), calories.end())?;
+        }
+
+        if self.suspicious.is_empty() {
+            write!(fmt, "suspicious: none")
+        } else {
+            write!(fmt, "suspicious:")?;
+
+            self.suspicious.iter().try_for_each(|suspicious| write!(fmt, "\n  {suspicious}"))
+        }
+    }
+}
+
+/// find the calories of the elf carrying the most
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    let elf_calorie_counter = ElfCalories::new(input);
-    let mut elf_calories = elf_calorie_counter
-        .into_iter()
-        .collect::<io::Result<Vec<_>>>()?;
-
-    elf_calories.sort_unstable_by(|a, b| b.cmp(a));
+    max_calories::<usize, _>(input).map(Answer::from)
+}
 
-    Ok(Box::new(elf_calories.into_iter().take(3).sum::<usize>()))
+/// find the total calories of the top three elves carrying the most
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
+    where R: Read
+{
+    total_calories(&top_elves::<usize, _>(input, TOP_ELVES)?).map(Answer::from)
 }
 
-#[cfg(test)]
-mod test {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
+/// number of elves carrying the most calories puzzle two totals
+const TOP_ELVES: usize = 3;
 
-    const INPUT: &str = "1000
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "1000
 2000
 3000
 
@@ -112,23 +461,157 @@ mod test {
 
 10000";
 
+/// day of the advent calendar
+const DAY: u8 = 1;
+
+/// title of the day's puzzle
+const TITLE: &str = "Calorie Counting";
+
+/// day 1 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        Inventories::<_, usize>::new(input).try_for_each(|inventory| inventory.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "24000" }]
+    }
+}
+
+/// day 1 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        Inventories::<_, usize>::new(input).try_for_each(|inventory| inventory.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "45000" }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::AocError;
+    use crate::solution::assert_examples;
+
+    use super::{calorie_statistics, ElfCalories, ElfTotal, EXAMPLE_INPUT, max_calories, Suspicious, top_elves, total_calories};
+
     #[test]
-    fn puzzle_one() {
-        let expected = "24000";
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn report_calorie_statistics() {
+        let statistics = calorie_statistics(EXAMPLE_INPUT.as_bytes()).expect("valid calories");
+
+        assert_eq!((statistics.elves, statistics.snacks, statistics.fewest_snacks, statistics.most_snacks), (5, 10, 1, 3));
+        assert!((statistics.mean_snacks - 2.0).abs() < 1e-9);
+        assert!((statistics.median_snacks - 2.0).abs() < 1e-9);
+        assert!((statistics.mean - 11_000.0).abs() < 1e-9);
+        assert!((statistics.median - 10_000.0).abs() < 1e-9);
+        assert!((statistics.std_dev - 6_985.7).abs() < 0.1);
+        assert_eq!(statistics.percentiles[0], (10, 4_000));
+        assert_eq!(statistics.percentiles[2], (50, 10_000));
+        assert_eq!(statistics.percentiles[5], (99, 24_000));
+        assert_eq!(statistics.histogram[0], (4_000..=6_000, 2));
+        assert_eq!(statistics.histogram.iter().map(|(_, elves)| elves).sum::<usize>(), 5);
+        assert!(statistics.suspicious.is_empty());
 
-        assert_eq!(actual, expected);
+        let statistics = calorie_statistics(&b"\n1\n2\n\n\n\n0\n\n5000000000"[..]).expect("valid calories");
+
+        assert_eq!(statistics.suspicious, [
+            Suspicious::EmptyGroups { line: 2, groups: 1 },
+            Suspicious::EmptyGroups { line: 7, groups: 2 },
+            Suspicious::NoCalories { elf: 2, line: 7 },
+            Suspicious::OverflowProne { elf: 3, line: 9, calories: 5_000_000_000 },
+        ]);
+        assert_eq!(calorie_statistics(&b""[..]).map(|statistics| statistics.elves).ok(), Some(0));
+
+        // blank lines after the last elf are as suspicious as before the first
+        let statistics = calorie_statistics(&b"\n1\n\n2\n3\n\n\n"[..]).expect("valid calories");
+
+        assert_eq!(statistics.suspicious, [
+            Suspicious::EmptyGroups { line: 2, groups: 1 },
+            Suspicious::EmptyGroups { line: 6, groups: 2 },
+        ]);
+        assert!((statistics.median_snacks - 1.5).abs() < 1e-9);
+
+        // totals past 64 bits, the range of the largest totals ends at the largest `u128`
+        let statistics = calorie_statistics(&b"0\n\n18446744073709551615\n1\n\n340282366920938463463374607431768211455"[..]);
+        let statistics = statistics.expect("valid calories");
+
+        assert_eq!(statistics.percentiles[2], (50, 1 << 64));
+        assert_eq!(statistics.histogram.first().map(|(_, elves)| *elves), Some(2));
+        assert_eq!(statistics.histogram.last().map(|(calories, elves)| (*calories.end(), *elves)), Some((u128::MAX, 1)));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "45000";
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn find_the_top_elves() {
+        let top = top_elves::<usize, _>(EXAMPLE_INPUT.as_bytes(), 2).expect("valid calories");
+
+        assert_eq!(top, [ElfTotal { elf: 4, calories: 24_000 }, ElfTotal { elf: 3, calories: 11_000 }]);
+
+        let top = top_elves::<usize, _>(&b"5\n\n7\n\n5"[..], 2).expect("valid calories");
 
-        assert_eq!(actual, expected);
+        assert_eq!(top.iter().map(|top| top.elf).collect::<Vec<_>>(), [2, 1]);
+        assert!(top_elves::<usize, _>(EXAMPLE_INPUT.as_bytes(), 0).is_ok_and(|top| top.is_empty()));
+        assert!(top_elves::<usize, _>(EXAMPLE_INPUT.as_bytes(), usize::MAX).is_ok_and(|top| top.len() == 5));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn report_overflowing_totals() {
+        let input = b"1\n\n18446744073709551615\n1\n\n2";
+
+        let Err(AocError::Overflow(record)) = max_calories::<u64, _>(&input[..]) else {
+            panic!("expected an overflow");
+        };
+
+        assert_eq!(record, "elf 2, day 1, line 4");
+        assert_eq!(max_calories::<u128, _>(&input[..]).ok(), Some(18_446_744_073_709_551_616));
+
+        let top = [ElfTotal { elf: 2, calories: u64::MAX }, ElfTotal { elf: 1, calories: 1 }];
+
+        assert!(matches!(total_calories(&top), Err(AocError::Overflow(record)) if record == "elf 1"));
+    }
+
+    #[test]
+    fn locate_invalid_calories() {
+        let input = EXAMPLE_INPUT.replace("8000", "8OOO");
+
+        let Err(AocError::Parse { line, column, .. }) = ElfCalories::<_, usize>::new(input.as_bytes()).collect::<Result<Vec<_>, _>>() else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (11, 2));
+    }
+
+    #[test]
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
+    }
+
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
+    }
+}
@@ -1,30 +1,100 @@
 //! [AOC 2022 Day 2](https://adventofcode.com/2022/day/2)
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
+pub mod rps;
+
+/// day of the advent calendar
+pub const DAY: u8 = 2;
 
-    const INPUT: &str = "A Y
+/// title of the day's puzzle
+pub const TITLE: &str = "Rock Paper Scissors";
+
+/// worked example from the puzzle text, shared by both puzzles
+pub const EXAMPLE_INPUT: &str = "A Y
 B X
 C Z";
 
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::solution::assert_examples;
+
+    use super::EXAMPLE_INPUT;
+    use super::rps::{best_decoding, Decoding, MoveDecoder, Outcome, OutcomeDecoder, Played, StrategyGuide, Symbol, total_score};
+
     #[test]
-    fn puzzle_one() {
-        let expected = "15";
-        let actual = crate::dec_02_one::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn decode_moves() {
+        let rounds = StrategyGuide::new(EXAMPLE_INPUT.as_bytes()).rounds(MoveDecoder::default())
+            .map(|round| round.map(|round| (round.you, round.score())))
+            .collect::<Result<Vec<_>, _>>();
+
+        assert_eq!(rounds.ok(), Some(vec![(Played::Paper, 8), (Played::Rock, 1), (Played::Scissors, 6)]));
 
-        assert_eq!(actual, expected);
+        let reversed = MoveDecoder::new([Played::Scissors, Played::Paper, Played::Rock]);
+
+        assert_eq!(total_score::<u32, _, _>(EXAMPLE_INPUT.as_bytes(), reversed).ok(), Some(8 + 9 + 7));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "12";
-        let actual = crate::dec_02_two::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn decode_outcomes() {
+        let rounds = StrategyGuide::new(EXAMPLE_INPUT.as_bytes()).rounds(OutcomeDecoder)
+            .map(|round| round.map(|round| (round.you, round.score())))
+            .collect::<Result<Vec<_>, _>>();
 
-        assert_eq!(actual, expected);
+        assert_eq!(rounds.ok(), Some(vec![(Played::Rock, 4), (Played::Rock, 1), (Played::Rock, 7)]));
+    }
+
+    #[test]
+    fn decode_with_your_own_decoder() {
+        // mirror the opponent whatever the symbol, every round is a draw
+        let mirror = |opponent: Played, _: Symbol| opponent;
+
+        assert_eq!(total_score::<u32, _, _>(EXAMPLE_INPUT.as_bytes(), mirror).ok(), Some(4 + 5 + 6));
+
+        let Some(Err(AocError::Parse { line, column, .. })) = StrategyGuide::new(&b"A Y\nB W"[..]).rounds(mirror).nth(1) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (2, 3));
+    }
+
+    #[test]
+    fn find_the_best_decoding() {
+        let decodings = best_decoding(EXAMPLE_INPUT.as_bytes()).expect("valid strategy guide");
+
+        assert_eq!(decodings.scores.len(), 7);
+        assert_eq!(decodings.scores[0], (Decoding::Moves(MoveDecoder::default()), 15));
+        assert_eq!(decodings.scores[6], (Decoding::Outcomes(OutcomeDecoder), 12));
+
+        // playing what beats each opponent's move wins every round
+        let best = MoveDecoder::new([Played::Scissors, Played::Paper, Played::Rock]);
+
+        assert_eq!((decodings.best, decodings.score), (Decoding::Moves(best), 24));
+        assert!(decodings.rounds.iter().all(|decoded| decoded.round.outcome() == Outcome::Win));
+        assert_eq!(decodings.rounds.iter().map(|decoded| decoded.symbol).collect::<Vec<_>>(), [Symbol::Y, Symbol::X, Symbol::Z]);
+        assert!(decodings.to_string().contains("round 2: X plays scissors against paper, win, scores 9"));
+    }
+
+    #[test]
+    fn report_overflowing_scores() {
+        let rounds = format!("{EXAMPLE_INPUT}\n\n").repeat(20);
+
+        let Err(AocError::Overflow(record)) = total_score::<u8, _, _>(rounds.as_bytes(), MoveDecoder::default()) else {
+            panic!("expected an overflow");
+        };
+
+        // 8 + 1 + 6 points a game of 3 rounds and a blank line, the 52nd round overflows 255 on line 69
+        assert_eq!(record, "day 2, line 69");
+        assert_eq!(total_score::<u128, _, _>(rounds.as_bytes(), MoveDecoder::default()).ok(), Some(300));
+        assert_eq!(total_score::<u16, _, _>(rounds.as_bytes(), OutcomeDecoder).ok(), Some(240));
+    }
+
+    #[test]
+    fn puzzle_one() {
+        assert_examples(&crate::dec_02_one::PuzzleOne);
+    }
+
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&crate::dec_02_two::PuzzleTwo);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,443 @@
+//! Rock, Paper, Scissors; the game model and the strategy guide of day 2, whose second column
+//! a [`StrategyDecoder`] interprets, i.e. as the move you should play or the outcome you should achieve
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+use std::iter;
+use std::str::FromStr;
+
+use nom::character::complete::alphanumeric1;
+use num_traits::{CheckedAdd, Zero};
+
+use crate::error::{AocError, ParseError};
+use crate::dec_02::DAY;
+use crate::utils::{CleansedLines, Line, sum_everything, TryIterator};
+use crate::utils::parse::{delimited_pair, parse_line};
+
+/// A move played, `A` rock, `B` paper or `C` scissors
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Played {
+    /// rock, scores 1
+    Rock = 1,
+    /// paper, scores 2
+    Paper = 2,
+    /// scissors, scores 3
+    Scissors = 3,
+}
+
+impl Played {
+    /// every move, in the order of their symbols
+    pub const ALL: [Self; 3] = [Self::Rock, Self::Paper, Self::Scissors];
+
+    /// every order of the moves, starting with `ALL`
+    pub const PERMUTATIONS: [[Self; 3]; 6] = [
+        [Self::Rock, Self::Paper, Self::Scissors],
+        [Self::Rock, Self::Scissors, Self::Paper],
+        [Self::Paper, Self::Rock, Self::Scissors],
+        [Self::Paper, Self::Scissors, Self::Rock],
+        [Self::Scissors, Self::Rock, Self::Paper],
+        [Self::Scissors, Self::Paper, Self::Rock],
+    ];
+}
+
+impl Display for Played {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.write_str(match self {
+            Self::Rock => "rock",
+            Self::Paper => "paper",
+            Self::Scissors => "scissors",
+        })
+    }
+}
+
+impl FromStr for Played {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(match source.trim().to_uppercase().as_str() {
+            "A" => Self::Rock,
+            "B" => Self::Paper,
+            "C" => Self::Scissors,
+            _ => return Err(ParseError::new(1, format!("{source:?} is not a valid move")))
+        })
+    }
+}
+
+/// Determine what to play for a desired outcome of an anticipated move played
+impl From<(Self, Outcome)> for Played {
+    fn from((played, desired_outcome): (Self, Outcome)) -> Self {
+        match (played, desired_outcome) {
+            (Self::Rock, Outcome::Draw) |
+            (Self::Paper, Outcome::Lose) |
+            (Self::Scissors, Outcome::Win) => Self::Rock,
+
+            (Self::Paper, Outcome::Draw) |
+            (Self::Scissors, Outcome::Lose) |
+            (Self::Rock, Outcome::Win) => Self::Paper,
+
+            (Self::Scissors, Outcome::Draw) |
+            (Self::Rock, Outcome::Lose) |
+            (Self::Paper, Outcome::Win) => Self::Scissors,
+        }
+    }
+}
+
+/// Outcome of a round for you
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Outcome {
+    /// a loss, scores 0
+    Lose = 0,
+    /// a draw, scores 3
+    Draw = 3,
+    /// a win, scores 6
+    Win = 6,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.write_str(match self {
+            Self::Lose => "lose",
+            Self::Draw => "draw",
+            Self::Win => "win",
+        })
+    }
+}
+
+/// Determine outcome of a game played (opponent, you)
+impl From<(Played, Played)> for Outcome {
+    fn from((opponent, you): (Played, Played)) -> Self {
+        match (opponent, you) {
+            (Played::Rock, Played::Paper) |
+            (Played::Paper, Played::Scissors) |
+            (Played::Scissors, Played::Rock) => Self::Win,
+
+            (Played::Rock, Played::Rock) |
+            (Played::Paper, Played::Paper) |
+            (Played::Scissors, Played::Scissors) => Self::Draw,
+
+            (Played::Rock, Played::Scissors) |
+            (Played::Paper, Played::Rock) |
+            (Played::Scissors, Played::Paper) => Self::Lose,
+        }
+    }
+}
+
+/// A round played, the opponent's move and yours
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Round {
+    /// the opponent's move
+    pub opponent: Played,
+    /// your move
+    pub you: Played,
+}
+
+impl Round {
+    /// outcome of the round for you
+    #[must_use]
+    pub fn outcome(self) -> Outcome {
+        Outcome::from((self.opponent, self.you))
+    }
+
+    /// your score of the round, the score of your move and of the outcome
+    #[must_use]
+    pub fn score(self) -> u8 {
+        self.outcome() as u8 + self.you as u8
+    }
+}
+
+/// An encrypted symbol of the second column of the strategy guide, `X`, `Y` or `Z`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Symbol {
+    /// the first symbol
+    X,
+    /// the second symbol
+    Y,
+    /// the third symbol
+    Z,
+}
+
+impl Symbol {
+    /// every symbol, in order
+    pub const ALL: [Self; 3] = [Self::X, Self::Y, Self::Z];
+}
+
+impl Display for Symbol {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{self:?}")
+    }
+}
+
+impl FromStr for Symbol {
+    type Err = ParseError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(match source.trim().to_uppercase().as_str() {
+            "X" => Self::X,
+            "Y" => Self::Y,
+            "Z" => Self::Z,
+            _ => return Err(ParseError::new(1, format!("{source:?} is not a valid symbol")))
+        })
+    }
+}
+
+/// Interprets the encrypted second column of the strategy guide as the move you play;
+/// closures of the opponent's move and a symbol are decoders too
+pub trait StrategyDecoder {
+    /// the move you play against the opponent's move for a symbol of the guide
+    fn decode(&self, opponent: Played, symbol: Symbol) -> Played;
+}
+
+impl<F> StrategyDecoder for F
+    where F: Fn(Played, Symbol) -> Played
+{
+    fn decode(&self, opponent: Played, symbol: Symbol) -> Played {
+        self(opponent, symbol)
+    }
+}
+
+/// Decodes the symbols as moves you should play, by default `X` rock, `Y` paper and `Z` scissors
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MoveDecoder {
+    moves: [Played; 3],
+}
+
+impl MoveDecoder {
+    /// decode `X`, `Y` and `Z` as the moves, in order
+    #[must_use]
+    pub const fn new(moves: [Played; 3]) -> Self {
+        Self { moves }
+    }
+}
+
+impl Default for MoveDecoder {
+    fn default() -> Self {
+        Self::new(Played::ALL)
+    }
+}
+
+impl StrategyDecoder for MoveDecoder {
+    fn decode(&self, _opponent: Played, symbol: Symbol) -> Played {
+        self.moves[symbol as usize]
+    }
+}
+
+/// `X rock, Y paper, Z scissors`
+impl Display for MoveDecoder {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let [x, y, z] = self.moves;
+
+        write!(fmt, "X {x}, Y {y}, Z {z}")
+    }
+}
+
+/// Decodes the symbols as outcomes you should achieve, `X` lose, `Y` draw and `Z` win
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct OutcomeDecoder;
+
+impl StrategyDecoder for OutcomeDecoder {
+    fn decode(&self, opponent: Played, symbol: Symbol) -> Played {
+        let outcome = match symbol {
+            Symbol::X => Outcome::Lose,
+            Symbol::Y => Outcome::Draw,
+            Symbol::Z => Outcome::Win,
+        };
+
+        Played::from((opponent, outcome))
+    }
+}
+
+/// `X lose, Y draw, Z win`
+impl Display for OutcomeDecoder {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.write_str("X lose, Y draw, Z win")
+    }
+}
+
+/// A decoding of the strategy guide the puzzle leaves open, the symbols as moves in any order or as outcomes
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Decoding {
+    /// the symbols are moves you should play
+    Moves(MoveDecoder),
+    /// the symbols are outcomes you should achieve
+    Outcomes(OutcomeDecoder),
+}
+
+impl Decoding {
+    /// every decoding; the 6 orders of moves, then outcomes
+    pub fn all() -> impl Iterator<Item=Self> {
+        Played::PERMUTATIONS.into_iter()
+            .map(|moves| Self::Moves(MoveDecoder::new(moves)))
+            .chain([Self::Outcomes(OutcomeDecoder)])
+    }
+}
+
+impl StrategyDecoder for Decoding {
+    fn decode(&self, opponent: Played, symbol: Symbol) -> Played {
+        match self {
+            Self::Moves(moves) => moves.decode(opponent, symbol),
+            Self::Outcomes(outcomes) => outcomes.decode(opponent, symbol),
+        }
+    }
+}
+
+impl Display for Decoding {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Moves(moves) => write!(fmt, "moves {moves}"),
+            Self::Outcomes(outcomes) => write!(fmt, "outcomes {outcomes}"),
+        }
+    }
+}
+
+/// A round of the strategy guide, your encrypted symbol and the round played as decoded
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DecodedRound {
+    /// your encrypted symbol
+    pub symbol: Symbol,
+    /// the round played
+    pub round: Round,
+}
+
+/// The score of every decoding of a strategy guide, and the rounds of the decoding scoring the most
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BestDecoding {
+    /// each decoding and its total score, in the order of [`Decoding::all`]
+    pub scores: Vec<(Decoding, usize)>,
+    /// the decoding scoring the most, the earliest of decodings scoring the same
+    pub best: Decoding,
+    /// total score of the best decoding
+    pub score: usize,
+    /// the rounds played by the best decoding
+    pub rounds: Vec<DecodedRound>,
+}
+
+/// scores of every decoding followed by the rounds of the best
+impl Display for BestDecoding {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for (decoding, score) in &self.scores {
+            writeln!(fmt, "{decoding}: {score}")?;
+        }
+
+        writeln!(fmt, "\nbest, {}: {}", self.best, self.score)?;
+
+        for (DecodedRound { symbol, round }, number) in self.rounds.iter().zip(1..) {
+            write!(fmt, "\nround {number}: {symbol} plays {} against {}, {}, scores {}",
+                   round.you, round.opponent, round.outcome(), round.score())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// scores the strategy guide under every decoding, finding the decoding that scores the most
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read, isn't valid or a score overflows
+pub fn best_decoding<R>(input: R) -> Result<BestDecoding, AocError>
+    where R: Read
+{
+    let mut plays = StrategyGuide::new(input);
+    let guide = iter::from_fn(|| Some(plays.next()?.map(|play| (Line(plays.line()), play))))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let decode = |decoding: Decoding| guide.iter()
+        .map(move |&(_, (opponent, symbol))| DecodedRound { symbol, round: Round { opponent, you: decoding.decode(opponent, symbol) } });
+
+    let scores = Decoding::all()
+        .map(|decoding| decode(decoding)
+            .map(|decoded| Ok(usize::from(decoded.round.score())))
+            .try_checked_sum(|round| AocError::overflow(format!("day {DAY}, {} decoded as {decoding}", guide[round - 1].0)))
+            .map(|score| (decoding, score)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // the first of the decodings scoring the most
+    let (best, score) = scores.iter()
+        .copied()
+        .reduce(|best, next| if next.1 > best.1 { next } else { best })
+        .unwrap_or_else(|| (Decoding::Moves(MoveDecoder::default()), 0));
+
+    Ok(BestDecoding { rounds: decode(best).collect(), scores, best, score })
+}
+
+/// Iterates a file with an encrypted strategy guide that contains
+/// the opponent's anticipated move and an encrypted symbol for you
+pub struct StrategyGuide<R> {
+    lines: CleansedLines<R>,
+}
+
+impl<R> StrategyGuide<R>
+    where R: Read
+{
+    /// iterate the rounds of an encrypted strategy guide
+    pub fn new(input: R) -> Self {
+        Self {
+            lines: CleansedLines::new(input)
+        }
+    }
+
+    /// line number of the last round read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.lines.line()
+    }
+
+    /// the rounds of the guide, the symbols decoded into your moves
+    pub fn rounds<D>(self, decoder: D) -> impl Iterator<Item=Result<Round, AocError>>
+        where D: StrategyDecoder
+    {
+        self.map(move |play| play.map(|(opponent, symbol)| Round { opponent, you: decoder.decode(opponent, symbol) }))
+    }
+}
+
+impl<R> Iterator for StrategyGuide<R>
+    where R: Read
+{
+    type Item = Result<(Played, Symbol), AocError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = match self.lines.next()? {
+            Ok(next) => next,
+            Err(err) => return Some(Err(err.into())),
+        };
+
+        return Some(strategy(&next).map_err(|err| err.in_line(DAY, self.lines.line(), &next)));
+
+        fn strategy(play: &str) -> Result<(Played, Symbol), ParseError> {
+            // each play should only contain two symbols, the opponent's play and your encrypted symbol
+            let (opponent, symbol) = parse_line(play, delimited_pair(alphanumeric1, " ", alphanumeric1))?;
+
+            // parse opponent's played move
+            let opponent = Played::from_str(opponent)
+                .map_err(|_| ParseError::at(play, opponent, format!("{opponent:?} is not a valid opponent move")))?;
+
+            // parse your encrypted symbol
+            let symbol = Symbol::from_str(symbol)
+                .map_err(|_| ParseError::at(play, symbol, format!("{symbol:?} is not a valid strategy")))?;
+
+            Ok((opponent, symbol))
+        }
+    }
+}
+
+/// total score of playing the strategy guide as decoded, in `N`; `u128` for stress inputs
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read, isn't valid or the score overflows `N`
+pub fn total_score<N, R, D>(input: R, decoder: D) -> Result<N, AocError>
+    where R: Read,
+          N: From<u8> + CheckedAdd + Zero,
+          D: StrategyDecoder
+{
+    let mut guide = StrategyGuide::new(input);
+    let scores = iter::from_fn(move || Some(guide.next()?.map(|(opponent, symbol)| {
+        let round = Round { opponent, you: decoder.decode(opponent, symbol) };
+
+        (Line(guide.line()), N::from(round.score()))
+    })));
+
+    sum_everything(DAY, scores)
+}
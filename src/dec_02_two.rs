@@ -1,141 +1,45 @@
 //! [AOC 2022 Day 2](https://adventofcode.com/2022/day/2)
 
-use std::io;
 use std::io::Read;
-use std::ops::ControlFlow;
-use std::str::FromStr;
 
-use crate::utils::{CleansedLines, io_error};
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::dec_02::{DAY, EXAMPLE_INPUT, TITLE};
+use crate::dec_02::rps::{OutcomeDecoder, StrategyGuide, total_score};
+use crate::solution::{Example, Mode, Solution};
 
-/// Iterates a file with an encrypted strategy guide that contains
-/// the opponent's anticipated move and the outcome you should achieve
-struct StrategyGuide<R> {
-    lines: CleansedLines<R>,
-}
-
-impl<R> StrategyGuide<R>
-    where R: Read
-{
-    fn new(input: R) -> Self {
-        Self {
-            lines: CleansedLines::new(input)
-        }
-    }
-}
-
-impl<R> Iterator for StrategyGuide<R>
+/// Play Rock, Paper, Scissors assuming the strategy guide is encrypted as the outcome of playing
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    type Item = io::Result<(Played, Outcome)>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        let next = self.lines.next()?.ok()?;
-
-        return Some(strategy(&next));
-
-        fn strategy(play: &str) -> io::Result<(Played, Outcome)> {
-            // each play should only contain two symbols, the opponent's play and your strategy
-            let (opponent, strategy) = play.split_once(' ')
-                .ok_or_else(
-                    || io_error(&format!("{play:?} is not a valid play strategy"))
-                )?;
-
-            // parse opponent's played move
-            let opponent = Played::from_str(opponent)
-                .map_err(|_| io_error(&format!("{opponent:?} is not a valid opponent move")))?;
-
-            // parse the strategy you should you
-            let strategy = Outcome::from_str(strategy)
-                .map_err(|_| io_error(&format!("{strategy:?} is not a valid strategy")))?;
-
-            Ok((opponent, strategy))
-        }
-    }
-}
-
-#[repr(usize)]
-#[derive(Clone, Copy)]
-enum Played {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
+    // calculate total score according to the strategy guide;
+    // playing a move that produces the suggested strategy
+    total_score::<usize, _, _>(input, OutcomeDecoder).map(Answer::from)
 }
 
+/// day 2 puzzle two
+pub struct PuzzleTwo;
 
-impl FromStr for Played {
-    type Err = ();
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
 
-    fn from_str(source: &str) -> Result<Self, Self::Err> {
-        Ok(match source.trim().to_uppercase().as_str() {
-            "A" => Self::Rock,
-            "B" => Self::Paper,
-            "C" => Self::Scissors,
-            _ => return Err(())
-        })
-    }
-}
+    fn part(&self) -> u8 { 2 }
 
-#[repr(u8)]
-#[derive(Clone, Copy)]
-enum Outcome {
-    Draw = 3,
-    Lose = 0,
-    Win = 6,
-}
+    fn title(&self) -> &'static str { TITLE }
 
-impl FromStr for Outcome {
-    type Err = ();
-
-    fn from_str(source: &str) -> Result<Self, Self::Err> {
-        Ok(match source.trim().to_uppercase().as_str() {
-            "X" => Self::Lose,
-            "Y" => Self::Draw,
-            "Z" => Self::Win,
-            _ => return Err(())
-        })
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
     }
-}
-
-/// Determine what to play for a desired outcome of an anticipated move played
-impl From<(Self, Outcome)> for Played {
-    fn from((played, desired_outcome): (Self, Outcome)) -> Self {
-        match (played, desired_outcome) {
-            (Self::Rock, Outcome::Draw) |
-            (Self::Paper, Outcome::Lose) |
-            (Self::Scissors, Outcome::Win) => Self::Rock,
-
-            (Self::Paper, Outcome::Draw) |
-            (Self::Scissors, Outcome::Lose) |
-            (Self::Rock, Outcome::Win) => Self::Paper,
 
-            (Self::Scissors, Outcome::Draw) |
-            (Self::Rock, Outcome::Lose) |
-            (Self::Paper, Outcome::Win) => Self::Scissors,
-        }
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        StrategyGuide::new(input).try_for_each(|play| play.map(drop))
     }
-}
-
-/// Play Rock, Paper, Scissors assuming the strategy guide is encrypted as the outcome of playing
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
-    where R: Read
-{
-    // calculate total score according to the strategy guide;
-    // playing a move that produces the suggested strategy
-    let total_score = StrategyGuide::new(input)
-        .try_fold(0, |acc, game_strategy| {
-            #[allow(clippy::option_if_let_else)] // map_or doesn't work because of move
-            if let Ok(game_strategy) = game_strategy {
-                let (opponent, outcome) = game_strategy;
-
-                ControlFlow::Continue(Played::from((opponent, outcome)) as usize + outcome as usize + acc)
-            } else {
-                ControlFlow::Break(game_strategy)
-            }
-        });
 
-    match total_score {
-        ControlFlow::Continue(ok) => Ok(Box::new(ok)),
-        ControlFlow::Break(Err(err)) => Err(err),
-        ControlFlow::Break(Ok(_)) => unreachable!()
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "12" }]
     }
 }
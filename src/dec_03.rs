@@ -1,33 +1,44 @@
 //! [AOC 2022 Day 3](https://adventofcode.com/2022/day/3)
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+/// worked example from the puzzle text, shared by both puzzles
+pub const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
 jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
 PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
 
+#[cfg(test)]
+mod tests {
+    use crate::dec_03_one::RummageRucksack;
+    use crate::dec_03_two::RummageRucksacks;
+    use crate::error::AocError;
+    use crate::solution::assert_examples;
+
+    use super::EXAMPLE_INPUT;
+
     #[test]
-    fn puzzle_one() {
-        let expected = "157";
-        let actual = crate::dec_03_one::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn find_the_only_common_item() {
+        let priorities = RummageRucksack::new(&b"abcAbc\nabcxyz"[..]).collect::<Vec<_>>();
 
-        assert_eq!(actual, expected);
+        assert!(matches!(priorities[..], [Err(AocError::Parse { line: 1, .. }), Err(AocError::Parse { line: 2, .. })]));
+
+        let input = EXAMPLE_INPUT.replace("PmmdzqPrVvPwwTWBwg", "Pmmdzq1rVvPwwTWBwg");
+
+        let Some(Err(AocError::Parse { line, column, .. })) = RummageRucksacks::new(input.as_bytes()).next() else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (3, 7));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "70";
-        let actual = crate::dec_03_two::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn puzzle_one() {
+        assert_examples(&crate::dec_03_one::PuzzleOne);
+    }
 
-        assert_eq!(actual, expected);
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&crate::dec_03_two::PuzzleTwo);
     }
-}
\ No newline at end of file
+}
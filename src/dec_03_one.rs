@@ -1,74 +1,178 @@
 //! [AOC 2022 Day 3](https://adventofcode.com/2022/day/3)
 
-use std::io;
 use std::io::Read;
+use std::iter;
 
-use crate::utils::{CleansedLines, find_matching, io_error, sum_everything};
+use crate::answer::Answer;
+use crate::error::{AocError, ParseError};
+use crate::dec_03::EXAMPLE_INPUT;
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{BitSet, CleansedLines, common_items, Line, sum_everything};
 
-/// Iterates a file of elf rucksacks and rummages around for improperly placed items
-struct RummageRucksack<R> {
+/// Iterates a file of elf rucksacks and rummages around for improperly placed items,
+/// yielding the priority of the item found in both compartments of each rucksack
+pub struct RummageRucksack<R> {
     lines: CleansedLines<R>,
 }
 
 impl<R> RummageRucksack<R>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// iterate the rucksacks of an input
+    pub fn new(input: R) -> Self {
         Self {
             lines: CleansedLines::new(input),
         }
     }
+
+    /// line number of the last rucksack read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.lines.line()
+    }
 }
 
 impl<R> Iterator for RummageRucksack<R>
     where R: Read
 {
-    type Item = io::Result<usize>;
+    type Item = Result<usize, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let rucksack = self.lines.next()?.ok()?;
+        let rucksack = match self.lines.next()? {
+            Ok(rucksack) => rucksack,
+            Err(err) => return Some(Err(err.into())),
+        };
 
-        if !rucksack.is_ascii() {
-            return Some(Err(io_error(&format!("Not all items in rucksack '{rucksack}' are valid items"))));
-        }
+        return Some(rummage(&rucksack).map_err(|err| err.in_line(DAY, self.lines.line(), &rucksack)));
 
-        let items = rucksack.len();
-        let compartment_items = items >> 1;
+        // find the priority of the item in both compartments of a rucksack
+        fn rummage(rucksack: &str) -> Result<usize, ParseError> {
+            if let Some(invalid) = rucksack.find(|itm: char| !itm.is_ascii()) {
+                return Err(ParseError::new(invalid + 1, format!("Not all items in rucksack '{rucksack}' are valid items")));
+            }
 
-        // rucksack compartments are supposed to be the same size
-        if items != compartment_items << 1 {
-            return Some(Err(io_error(&format!("'{rucksack}' does not have the same number of items in each of two compartments"))));
-        }
+            let items = rucksack.len();
+            let compartment_items = items >> 1;
+
+            // rucksack compartments are supposed to be the same size
+            if items != compartment_items << 1 {
+                return Err(ParseError::new(1, format!("'{rucksack}' does not have the same number of items in each of two compartments")));
+            }
 
-        // split rucksack into its compartments
-        let compartment_a = rucksack[0..compartment_items].chars();
-        let compartment_b = rucksack[compartment_items..].chars();
+            // split rucksack into its compartments
+            let compartments = [&rucksack[..compartment_items], &rucksack[compartment_items..]];
 
-        // find first matching item in each rucksack compartment
-        let match_results = find_matching(
-            &[compartment_a, compartment_b],
-            |itm| itm.is_alphabetic()
-                .then_some(())
-                .ok_or_else(|| io_error("Not all items in rucksacks are valid items")),
-            io_error,
-        );
+            // the item in both rucksack compartments
+            let common = common_items(compartments.map(str::chars), |itm| item_priority(rucksack, itm))?;
 
-        match_results.map_or(None, |found| Some(Ok(prioritize_rucksack_item(found))))
+            only_item(common, "both compartments").map_err(|msg| ParseError::new(1, msg))
+        }
     }
 }
 
 /// find the miss items in compartments of a rucksack
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
+    where R: Read
+{
+    let mut rucksacks = RummageRucksack::new(input);
+    let priorities = iter::from_fn(|| Some(rucksacks.next()?.map(|priority| (Line(rucksacks.line()), priority))));
+
+    sum_everything(DAY, priorities).map(Answer::from)
+}
+
+/// reads the rucksacks of an input, checking every item is valid without rummaging through them
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or an item isn't valid
+pub fn parse_rucksacks<R>(input: R) -> Result<(), AocError>
     where R: Read
 {
-    Ok(sum_everything(RummageRucksack::new(input)).map(Box::new)?)
+    for numbered in CleansedLines::new(input).numbered() {
+        let (line, rucksack) = numbered?;
+
+        for item in rucksack.chars() {
+            item_priority(&rucksack, item).map_err(|err| err.in_line(DAY, line, &rucksack))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// an error of an invalid item in a rucksack, at the item
+#[must_use]
+pub fn invalid_item(rucksack: &str, item: char) -> ParseError {
+    let column = rucksack.find(item).map_or(1, |idx| idx + 1);
+
+    ParseError::new(column, format!("'{item}' is not a valid item"))
+}
+
+/// the priority of a rucksack item, its index in the alphabet of items; or why it isn't an item
+///
+/// # Errors
+///
+/// fails if the item isn't a letter
+pub fn item_priority(rucksack: &str, item: char) -> Result<usize, ParseError> {
+    if item.is_ascii_alphabetic() {
+        Ok(prioritize_rucksack_item(item))
+    } else {
+        Err(invalid_item(rucksack, item))
+    }
+}
+
+/// the priority of the only item common to the rucksacks, or why there isn't exactly one
+///
+/// # Errors
+///
+/// fails if no item or more than one item is in `every` rucksack
+pub fn only_item(common: BitSet<u64>, every: &str) -> Result<usize, String> {
+    let mut items = common.iter();
+
+    match (items.next(), common.len()) {
+        (Some(priority), 1) => Ok(priority),
+        (None, _) => Err(format!("no item is in {every}")),
+        (_, found) => Err(format!("{found} items are in {every}, expected one")),
+    }
 }
 
 /// convert rucksack item into it priority
+#[must_use]
 pub const fn prioritize_rucksack_item(found: char) -> usize {
     if found.is_ascii_uppercase() {
         found as usize - 38
     } else {
         found as usize - 96
     }
-}
\ No newline at end of file
+}
+
+/// day of the advent calendar
+pub const DAY: u8 = 3;
+
+/// title of the day's puzzle
+pub const TITLE: &str = "Rucksack Reorganization";
+
+/// day 3 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_rucksacks(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "157" }]
+    }
+}
@@ -1,72 +1,109 @@
 //! [AOC 2022 Day 3](https://adventofcode.com/2022/day/3)
 
-use std::io;
 use std::io::Read;
+use std::iter;
 
-use crate::dec_03_one::prioritize_rucksack_item;
-use crate::utils::{CleansedLines, find_matching, io_error, sum_everything};
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::dec_03::EXAMPLE_INPUT;
+use crate::dec_03_one::{DAY, item_priority, only_item, parse_rucksacks, TITLE};
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{CleansedLines, common_items, Line, sum_everything};
 
-/// Iterates a file of elf rucksacks and rummages around for improperly placed items
-struct RummageRucksacks<R> {
+/// Iterates a file of elf rucksacks in groups of three, yielding
+/// the priority of the badge item found in every rucksack of a group
+pub struct RummageRucksacks<R> {
     lines: CleansedLines<R>,
 }
 
 impl<R> RummageRucksacks<R>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// iterate the rucksacks of an input
+    pub fn new(input: R) -> Self {
         Self {
             lines: CleansedLines::new(input),
         }
     }
+
+    /// line number of the last rucksack read, the last of its group, starting at 1
+    pub const fn line(&self) -> usize {
+        self.lines.line()
+    }
 }
 
 impl<R> Iterator for RummageRucksacks<R>
     where R: Read
 {
-    type Item = io::Result<usize>;
+    type Item = Result<usize, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         const NOT_ENOUGH: &str = "Not enough rucksacks to make a required group";
 
         let group_a = match self.lines.next() {
-            Some(Ok(group)) => group,
-            Some(Err(err)) => return Some(Err(err)),
+            Some(Ok(group)) => (self.lines.line(), group),
+            Some(Err(err)) => return Some(Err(err.into())),
             None => return None,
         };
         let group_b = match self.lines.next() {
-            Some(Ok(group)) => group,
-            Some(Err(err)) => return Some(Err(err)),
-            None => return Some(Err(io_error(NOT_ENOUGH))),
+            Some(Ok(group)) => (self.lines.line(), group),
+            Some(Err(err)) => return Some(Err(err.into())),
+            None => return Some(Err(AocError::logic(NOT_ENOUGH))),
         };
         let group_c = match self.lines.next() {
-            Some(Ok(group)) => group,
-            Some(Err(err)) => return Some(Err(err)),
-            None => return Some(Err(io_error(NOT_ENOUGH))),
+            Some(Ok(group)) => (self.lines.line(), group),
+            Some(Err(err)) => return Some(Err(err.into())),
+            None => return Some(Err(AocError::logic(NOT_ENOUGH))),
         };
 
-        let group_a = group_a.chars();
-        let group_b = group_b.chars();
-        let group_c = group_c.chars();
+        // the badge is the item in each rucksack of a group of three
+        let rucksacks = [&group_a, &group_b, &group_c];
 
-        // group rucksacks into groups of three
-        let elf_groups = [group_a, group_b, group_c];
-
-        let match_results = find_matching(
-            &elf_groups,
-            |itm| itm.is_alphabetic()
-                .then_some(())
-                .ok_or_else(|| io_error("Not all items in rucksacks are valid items")),
-            io_error,
+        let common = common_items(
+            rucksacks.map(|(line, rucksack)| rucksack.chars().map(move |itm| (*line, rucksack, itm))),
+            |(line, rucksack, itm)| item_priority(rucksack, itm).map_err(|err| err.in_line(DAY, line, rucksack)),
         );
 
-        match_results.map_or(None, |found| Some(Ok(prioritize_rucksack_item(found))))
+        Some(common.and_then(
+            |common| only_item(common, "every rucksack of the group")
+                .map_err(|msg| AocError::logic(format!("{msg}, the group of rucksacks starting at line {}", group_a.0)))
+        ))
     }
 }
 
-// solve some puzzle one
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// find the badge item shared by each group of three elves
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    Ok(sum_everything(RummageRucksacks::new(input)).map(Box::new)?)
+    let mut groups = RummageRucksacks::new(input);
+    let badges = iter::from_fn(|| Some(groups.next()?.map(|priority| (Line(groups.line()), priority))));
+
+    sum_everything(DAY, badges).map(Answer::from)
+}
+
+/// day 3 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_rucksacks(input)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "70" }]
+    }
 }
@@ -1,28 +1,33 @@
 //! [AOC 2022 Day 4](https://adventofcode.com/2022/day/4)
 
 use std::cmp::{max, min};
-use std::io;
-use std::io::{Error, Read};
+use std::io::Read;
 use std::ops::RangeInclusive;
 
-use crate::utils::{CleansedLines, io_error, sum_everything};
+use crate::answer::Answer;
+use crate::error::{AocError, ParseError};
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{CleansedLines, TryIterator};
+use crate::utils::parse::{delimited_pair, parse_line, range};
 
-/// Iterates a file of elf chore assignment pairs
-struct ElfChoreAssignmentsPairs<R> {
+/// Iterates a file of elf chore assignment pairs, yielding the section ranges of each pair
+pub struct ElfChoreAssignmentsPairs<R> {
     lines: CleansedLines<R>,
 }
 
 impl<R> ElfChoreAssignmentsPairs<R>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// iterate the assignment pairs of an input
+    pub fn new(input: R) -> Self {
         Self {
             lines: CleansedLines::new(input),
         }
     }
 }
 
-type PairResult = io::Result<(RangeInclusive<usize>, RangeInclusive<usize>)>;
+/// section ranges assigned to a pair of elves, or why they couldn't be parsed
+pub type PairResult = Result<(RangeInclusive<usize>, RangeInclusive<usize>), AocError>;
 
 impl<R> Iterator for ElfChoreAssignmentsPairs<R>
     where R: Read
@@ -30,114 +35,123 @@ impl<R> Iterator for ElfChoreAssignmentsPairs<R>
     type Item = PairResult;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let pair = self.lines.next()?.ok()?;
+        let pair = match self.lines.next()? {
+            Ok(pair) => pair,
+            Err(err) => return Some(Err(err.into())),
+        };
 
-        return Some(parse_pair(&pair));
+        return Some(parse_pair(&pair).map_err(|err| err.in_line(DAY, self.lines.line(), &pair)));
 
-        // splits a pair of ranges
-        fn parse_pair(pair: &str) -> PairResult {
-            let (section_a, section_b) = split_clean(
-                pair, ',',
-                || format!("'{pair}' is invalid"), )?;
-
-            let section_a = parse_section(section_a, "first", pair)?;
-            let section_b = parse_section(section_b, "Second", pair)?;
-
-            Ok((section_a, section_b))
-        }
-
-        // parse a section's range
-        fn parse_section(section: &str, name: &str, pair: &str) -> io::Result<RangeInclusive<usize>> {
-            let (start, end) = split_clean(
-                section, '-',
-                || format!("{name} section '{section}' in pair '{pair}' is invalid"),
-            )?;
-
-            let parse = |val: &str, which: &str| val.parse::<usize>()
-                .map_err(|err| io_error(&format!("{which} value for {name} section '{section}' in pair '{pair}' is invalid; {err}")));
-
-            let start = parse(start, "start")?;
-            let end = parse(end, "start")?;
-
-            Ok(start..=end)
-        }
-
-        // clean splits a delimited value
-        fn split_clean<Err>(pair: &str, delimiter: char, error: Err) -> Result<(&str, &str), Error>
-            where Err: Fn() -> String
-        {
-            pair.split_once(delimiter)
-                .ok_or_else(|| io_error(&error()))
-                .map(|(a, b)| (a.trim(), b.trim()))
+        // a pair of section ranges, i.e. `2-4,6-8`
+        fn parse_pair(pair: &str) -> Result<(RangeInclusive<usize>, RangeInclusive<usize>), ParseError> {
+            parse_line(pair, delimited_pair(range, ",", range))
         }
     }
 }
 
-// finds all pairs with contained sections
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// finds all pairs with contained sections
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    let chores = ElfChoreAssignmentsPairs::new(input);
-
-    let contained_chores = chores.into_iter()
-        .map(
-            |pair| pair
-                .map(|(a, b)|
-                    u32::from(
-                        (a.start() <= b.start() && a.end() >= b.end()) ||
-                            (a.start() >= b.start() && a.end() <= b.end())
-                    )
-                )
-        );
-
-    Ok(sum_everything(contained_chores).map(Box::new)?)
+    let contained_chores = ElfChoreAssignmentsPairs::new(input)
+        .try_count_where(
+            |(a, b)|
+                (a.start() <= b.start() && a.end() >= b.end()) ||
+                    (a.start() >= b.start() && a.end() <= b.end())
+        )?;
+
+    Ok(Answer::from(contained_chores))
 }
 
-// finds all pairs with overlapping sections
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// finds all pairs with overlapping sections
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    let chores = ElfChoreAssignmentsPairs::new(input);
+    let overlapping_chores = ElfChoreAssignmentsPairs::new(input)
+        .try_count_where(|(a, b)| max(a.start(), b.start()) <= min(a.end(), b.end()))?;
 
-    let overlapping_chores = chores.into_iter()
-        .map(
-            |pair| pair
-                .map(|(a, b)|
-                    u32::from(max(a.start(), b.start()) <= min(a.end(), b.end()))
-                )
-        );
-
-    Ok(sum_everything(overlapping_chores).map(Box::new)?)
+    Ok(Answer::from(overlapping_chores))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const INPUT: &str = "2-4,6-8
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "2-4,6-8
 2-3,4-5
 5-7,7-9
 2-8,3-7
 6-6,4-6
 2-6,4-8";
 
+/// day of the advent calendar
+const DAY: u8 = 4;
+
+/// title of the day's puzzle
+const TITLE: &str = "Camp Cleanup";
+
+/// day 4 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        ElfChoreAssignmentsPairs::new(input).try_for_each(|pair| pair.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "2" }]
+    }
+}
+
+/// day 4 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        ElfChoreAssignmentsPairs::new(input).try_for_each(|pair| pair.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "4" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::assert_examples;
+
     #[test]
     fn puzzle_one() {
-        let expected = "2";
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
-
-        assert_eq!(actual, expected);
+        assert_examples(&super::PuzzleOne);
     }
 
     #[test]
     fn puzzle_two() {
-        let expected = "4";
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
-
-        assert_eq!(actual, expected);
+        assert_examples(&super::PuzzleTwo);
     }
-}
\ No newline at end of file
+}
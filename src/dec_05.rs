@@ -7,41 +7,52 @@ use std::io;
 use std::io::Read;
 use std::iter::Peekable;
 
-use nom::bytes::complete::tag;
-use nom::character::complete;
-use nom::character::complete::{alphanumeric1, space0, space1};
-use nom::IResult;
-
-use crate::utils::{CleansedLines, io_error};
-
-type Crates = VecDeque<String>;
-type Labels = Vec<String>;
-type Moves = Vec<Move>;
-type Stacks = HashMap<String, Crates>;
+use nom::character::complete::{alphanumeric1, space0};
+use nom::combinator::map;
+use nom::sequence::tuple;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::CleansedLines;
+use crate::utils::parse::{keyword, literal, parse_line, Parsed, unsigned};
+
+/// crates of a stack, from the top down
+pub type Crates = VecDeque<String>;
+/// labels of the stacks, in input order
+pub type Labels = Vec<String>;
+/// move instructions, in input order
+pub type Moves = Vec<Move>;
+/// stacks of crates by label
+pub type Stacks = HashMap<String, Crates>;
 
 /// Move instruction
 #[derive(Debug)]
-struct Move {
+pub struct Move {
     /// Number of crates to move
-    crates: usize,
+    pub crates: usize,
     /// Source crate; move from
-    from: String,
+    pub from: String,
     /// Destination crate; move to
-    to: String,
+    pub to: String,
 }
 
-/// parse input instructions
+/// parse input instructions; the stack labels, the starting stacks of crates and the moves
+///
+/// # Errors
+///
+/// fails if the input can't be read or isn't valid
 #[allow(clippy::too_many_lines)]
-fn parse_instructions<R>(input: R) -> io::Result<(Labels, Stacks, Moves)>
+pub fn parse_instructions<R>(input: R) -> Result<(Labels, Stacks, Moves), AocError>
     where R: Read
 {
-    let input = CleansedLines::new(input).peekable();
+    let input = CleansedLines::new(input).numbered().peekable();
     let (input, stacks) = parse_stack_crates(input)?;
     let (input, stack_labels) = parse_stack_labels(input)?;
 
     // check the number of stacks inputted matches the number labels parsed
     if stacks.len() != stack_labels.len() {
-        return Err(io_error(&format!(
+        return Err(AocError::logic(format!(
             "number of stacks {} does not match number of stack labels {}",
             stacks.len(),
             stack_labels.len())
@@ -54,87 +65,65 @@ fn parse_instructions<R>(input: R) -> io::Result<(Labels, Stacks, Moves)>
     let stacks = stack_labels
         .clone()
         .into_iter()
-        .zip(stacks.into_iter())
+        .zip(stacks)
         .collect::<HashMap<String, Crates>>();
 
     return Ok((stack_labels, stacks, moves));
 
-    fn parse_move_instructions<I>(mut input: I) -> io::Result<Moves>
-        where I: Iterator<Item=io::Result<String>>,
+    fn parse_move_instructions<I>(mut input: I) -> Result<Moves, AocError>
+        where I: Iterator<Item=io::Result<(usize, String)>>,
     {
         let mut moves = Moves::new();
 
         loop {
-            let instruction = match input.next() {
+            let (line, instruction) = match input.next() {
                 None if moves.is_empty() =>
-                    return Err(io_error("no move instructions found")),
+                    return Err(AocError::logic("no move instructions found")),
                 None =>
                     break,
                 Some(instructions) =>
-                    instructions.map_err(
-                        |err| io_error(&format!("exception reading next move instruction; {err}"))
-                    )?,
+                    instructions?,
             };
 
-            let (_, r#move) = parse_move(&instruction)
-                .map_err(
-                    |err| io_error(&format!("couldn't parsing move instruction '{instruction}'; {err}"))
-                )?;
+            let r#move = parse_line(&instruction, parse_move)
+                .map_err(|err| err.in_line(DAY, line, &instruction))?;
 
             moves.push(r#move);
         }
 
         return Ok(moves);
 
-        fn parse_move(instruction: &str) -> IResult<&str, Move> {
-            let (next, _) = tag("move")(instruction)?;
-            let (next, _) = space1(next)?;
-            let (next, crates) = complete::u32(next)?;
-            let (next, _) = space1(next)?;
-            let (next, _) = tag("from")(next)?;
-            let (next, _) = space1(next)?;
-            let (next, from) = alphanumeric1(next)?;
-            let (next, _) = space1(next)?;
-            let (next, _) = tag("to")(next)?;
-            let (next, _) = space1(next)?;
-            let (next, to) = alphanumeric1(next)?;
-
-            let r#move = Move {
-                crates: crates as usize,
-                from: from.to_string(),
-                to: to.to_string(),
-            };
-
-            Ok((next, r#move))
+        // i.e. `move 1 from 2 to 1`
+        fn parse_move(instruction: &str) -> Parsed<'_, Move> {
+            map(
+                tuple((keyword("move", unsigned), keyword("from", alphanumeric1), keyword("to", alphanumeric1))),
+                |(crates, from, to)| Move { crates, from: from.to_string(), to: to.to_string() },
+            )(instruction)
         }
     }
 
-    fn parse_stack_crates<I>(mut input: Peekable<I>) -> io::Result<(Peekable<I>, Vec<Crates>)>
+    fn parse_stack_crates<I>(mut input: Peekable<I>) -> Result<(Peekable<I>, Vec<Crates>), AocError>
         where
-            I: Iterator<Item=io::Result<String>>,
+            I: Iterator<Item=io::Result<(usize, String)>>,
     {
         let mut stacks = Vec::new();
 
         loop {
-            if let Some(Ok(next)) = input.peek() {
+            if let Some(Ok((_, next))) = input.peek() {
                 if !next.contains('[') { break; }
             }
 
-            let crates = match input.next() {
+            let (line, crates) = match input.next() {
                 None if stacks.is_empty() =>
-                    return Err(io_error("no crate contents found")),
+                    return Err(AocError::logic("no crate contents found")),
                 None =>
                     break,
                 Some(crates) =>
-                    crates.map_err(
-                        |err| io_error(&format!("exception reading crate contents; {err}"))
-                    )?,
+                    crates?,
             };
 
-            let (_, crates) = parse_crates(&crates)
-                .map_err(
-                    |err| io_error(&format!("couldn't parse crate contents '{crates}'; {err}"))
-                )?;
+            let crates = parse_line(&crates, parse_crates)
+                .map_err(|err| err.in_line(DAY, line, &crates))?;
 
             while stacks.len() < crates.len() {
                 stacks.push(Crates::new());
@@ -149,7 +138,7 @@ fn parse_instructions<R>(input: R) -> io::Result<(Labels, Stacks, Moves)>
 
         return Ok((input, stacks));
 
-        fn parse_crates(input: &str) -> IResult<&str, Vec<Option<String>>> {
+        fn parse_crates(input: &str) -> Parsed<'_, Vec<Option<String>>> {
             let mut crates = Vec::new();
             let mut stack = 0;
             let mut remainder = input.trim_end();
@@ -160,9 +149,9 @@ fn parse_instructions<R>(input: R) -> io::Result<(Labels, Stacks, Moves)>
                 stack += empty.len() >> 2;
 
                 if next.contains('[') {
-                    let (next, _) = tag("[")(next)?;
+                    let (next, _) = literal("[")(next)?;
                     let (next, contents) = alphanumeric1(next)?;
-                    let (next, _) = tag("]")(next)?;
+                    let (next, _) = literal("]")(next)?;
 
                     while crates.len() < stack {
                         crates.push(None);
@@ -183,13 +172,12 @@ fn parse_instructions<R>(input: R) -> io::Result<(Labels, Stacks, Moves)>
         }
     }
 
-    fn parse_stack_labels<I>(mut input: I) -> io::Result<(I, Labels)>
-        where I: Iterator<Item=io::Result<String>>,
+    fn parse_stack_labels<I>(mut input: I) -> Result<(I, Labels), AocError>
+        where I: Iterator<Item=io::Result<(usize, String)>>,
     {
-        let labels = input
+        let (_, labels) = input
             .next()
-            .ok_or_else(|| io_error("could not find any labels"))?
-            .map_err(|err| io_error(&format!("exception reading stack labels; {err}")))?;
+            .ok_or_else(|| AocError::logic("could not find any labels"))??;
 
         let labels = labels
             .split(' ')
@@ -200,9 +188,13 @@ fn parse_instructions<R>(input: R) -> io::Result<(Labels, Stacks, Moves)>
     }
 }
 
-// finds crates at the top of each stack after all of the move instructions;
-// moving each crate one at a time
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// finds crates at the top of each stack after all of the move instructions;
+/// moving each crate one at a time
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     let (labels, mut stacks, instructions) = parse_instructions(input)?;
@@ -211,24 +203,28 @@ pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
         for _ in 0..r#move.crates {
             let from_crate = stacks
                 .get_mut(&r#move.from)
-                .ok_or_else(|| io_error(&format!("could not find stack '{}'", r#move.from)))?
+                .ok_or_else(|| AocError::logic(format!("could not find stack '{}'", r#move.from)))?
                 .pop_front()
-                .ok_or_else(|| io_error(&format!("expected more crates on stack '{}'", r#move.from)))?;
+                .ok_or_else(|| AocError::logic(format!("expected more crates on stack '{}'", r#move.from)))?;
 
             let to_crate = stacks
                 .get_mut(&r#move.to)
-                .ok_or_else(|| io_error(&format!("could not find stack '{}'", r#move.to)))?;
+                .ok_or_else(|| AocError::logic(format!("could not find stack '{}'", r#move.to)))?;
 
             to_crate.push_front(from_crate);
         }
     }
 
-    Ok(Box::new(top_crate_off_all_stacks(labels, stacks)))
+    Ok(Answer::from(top_crate_off_all_stacks(labels, stacks)))
 }
 
-// finds crates at the top of each stack after all of the move instructions;
-// moving all crates,to be moved, at once preserving stacking order
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// finds crates at the top of each stack after all of the move instructions;
+/// moving all crates,to be moved, at once preserving stacking order
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     let (labels, mut stacks, instructions) = parse_instructions(input)?;
@@ -238,39 +234,38 @@ pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
 
         let from_crate = stacks
             .get_mut(&r#move.from)
-            .ok_or_else(|| io_error(&format!("could not find stack '{}'", r#move.from)))?;
+            .ok_or_else(|| AocError::logic(format!("could not find stack '{}'", r#move.from)))?;
 
         for _ in 0..r#move.crates {
             let from_crate = from_crate
                 .pop_front()
-                .ok_or_else(|| io_error(&format!("expected more crates on stack '{}'", r#move.from)))?;
+                .ok_or_else(|| AocError::logic(format!("expected more crates on stack '{}'", r#move.from)))?;
 
             moved_crates.push_front(from_crate);
         }
 
         let to_crate = stacks
             .get_mut(&r#move.to)
-            .ok_or_else(|| io_error(&format!("could not find stack '{}'", r#move.to)))?;
+            .ok_or_else(|| AocError::logic(format!("could not find stack '{}'", r#move.to)))?;
 
         for moved_crate in moved_crates {
             to_crate.push_front(moved_crate);
         }
     }
 
-    Ok(Box::new(top_crate_off_all_stacks(labels, stacks)))
+    Ok(Answer::from(top_crate_off_all_stacks(labels, stacks)))
 }
 
-fn top_crate_off_all_stacks(labels: Labels, mut stacks: Stacks) -> String {
+/// takes the top crate off of every stack, in label order
+#[must_use]
+pub fn top_crate_off_all_stacks(labels: Labels, mut stacks: Stacks) -> String {
     labels.into_iter()
         .filter_map(|lbl| stacks.get_mut(&lbl)?.pop_front())
         .collect()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const INPUT: &str = "    [D]
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "    [D]
 [N] [C]
 [Z] [M] [P]
  1   2   3
@@ -280,23 +275,83 @@ move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
 
+/// day of the advent calendar
+const DAY: u8 = 5;
+
+/// title of the day's puzzle
+const TITLE: &str = "Supply Stacks";
+
+/// day 5 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_instructions(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "CMZ" }]
+    }
+}
+
+/// day 5 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_instructions(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "MCD" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::solution::assert_examples;
+
+    use super::{EXAMPLE_INPUT, parse_instructions};
+
     #[test]
-    fn puzzle_one() {
-        let expected = "CMZ";
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn locate_invalid_instructions() {
+        let input = EXAMPLE_INPUT.replace("move 2 from 2 to 1", "move 2 form 2 to 1");
 
-        assert_eq!(actual, expected);
+        let Err(AocError::Parse { line, column, snippet, .. }) = parse_instructions(input.as_bytes()) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column, snippet.as_str()), (8, 8, "move 2 form 2 to 1"));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "MCD";
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
+    }
 
-        assert_eq!(actual, expected);
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
     }
-}
\ No newline at end of file
+}
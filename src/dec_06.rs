@@ -1,46 +1,59 @@
 //! [AOC 2022 Day 6](https://adventofcode.com/2022/day/6)
 
-use std::io;
 use std::io::{BufReader, Read};
 
-use crate::utils::io_error;
+use crate::answer::Answer;
+use crate::error::{AocError, ParseError};
+use crate::solution::{Example, Mode, Solution};
 
-// find pack start signal
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// find pack start signal
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     const PACKET_START_MARKER_SIZE: usize = 4;
 
     let data_stream = read_data_stream(input)?;
 
-    Ok(find_marker_start(&data_stream, PACKET_START_MARKER_SIZE)
-        .ok_or_else(|| io_error("no pack start signal found"))
-        .map(Box::new)?)
+    find_marker_start(&data_stream, PACKET_START_MARKER_SIZE)
+        .ok_or_else(|| AocError::logic("no pack start signal found"))
+        .map(Answer::from)
 }
 
-// find start of message signal
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// find start of message signal
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     const MESSAGE_START_MARKER_SIZE: usize = 14;
 
     let data_stream = read_data_stream(input)?;
 
-    Ok(find_marker_start(&data_stream, MESSAGE_START_MARKER_SIZE)
-        .ok_or_else(|| io_error("no message start signal found"))
-        .map(Box::new)?)
+    find_marker_start(&data_stream, MESSAGE_START_MARKER_SIZE)
+        .ok_or_else(|| AocError::logic("no message start signal found"))
+        .map(Answer::from)
 }
 
-fn find_marker_start(data_stream: &str, marker_length: usize) -> Option<usize> {
+/// finds the position just past the first marker of distinct bytes in a data stream,
+/// the position is in bytes, which are characters of an ascii data stream
+#[must_use]
+pub fn find_marker_start(data_stream: &str, marker_length: usize) -> Option<usize> {
+    let data_stream = data_stream.as_bytes();
     // start of marker
     let mut from = 0;
     // window of marker in data stream
-    let mut marker = &data_stream[from..1];
+    let mut marker = data_stream.get(from..1)?;
 
-    // iterate data stream one char at a time
-    for (idx, next) in data_stream.chars().skip(1).enumerate() {
-        // check if next char in stream exists marker
-        if let Some(found) = marker.find(next) {
+    // iterate data stream one byte at a time
+    for (idx, next) in data_stream.iter().skip(1).enumerate() {
+        // check if next byte in stream exists marker
+        if let Some(found) = marker.iter().position(|byte| byte == next) {
             // if found move start of marker window past duplicate value
             from += found + 1;
         }
@@ -57,54 +70,132 @@ fn find_marker_start(data_stream: &str, marker_length: usize) -> Option<usize> {
     None
 }
 
-fn read_data_stream<R>(input: R) -> io::Result<String>
+/// reads an ascii data stream, failing at the first character that isn't ascii
+fn read_data_stream<R>(input: R) -> Result<String, AocError>
     where R: Read
 {
     let mut data_stream = String::new();
 
     BufReader::new(input).read_to_string(&mut data_stream)?;
 
+    if let Some(invalid) = data_stream.find(|signal: char| !signal.is_ascii()) {
+        let start = data_stream[..invalid].rfind('\n').map_or(0, |newline| newline + 1);
+        let end = data_stream[invalid..].find('\n').map_or(data_stream.len(), |newline| invalid + newline);
+        let line = data_stream[..invalid].matches('\n').count() + 1;
+        let column = data_stream[start..invalid].chars().count() + 1;
+
+        return Err(ParseError::new(column, "expected an ascii data stream")
+            .in_line(DAY, line, &data_stream[start..end]));
+    }
+
     Ok(data_stream)
 }
 
+//noinspection SpellCheckingInspection
+/// worked examples from the puzzle text
+const EXAMPLE_INPUTS: [&str; 5] = [
+    "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+    "bvwbjplbgvbhsrlpgdmjqwftvncz",
+    "nppdvjthqldpwncqszvftbrmjlhg",
+    "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
+    "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
+];
+
+/// worked examples of puzzle one
+const PUZZLE_ONE_EXAMPLES: [Example; 5] = [
+    Example { input: EXAMPLE_INPUTS[0], expected: "7" },
+    Example { input: EXAMPLE_INPUTS[1], expected: "5" },
+    Example { input: EXAMPLE_INPUTS[2], expected: "6" },
+    Example { input: EXAMPLE_INPUTS[3], expected: "10" },
+    Example { input: EXAMPLE_INPUTS[4], expected: "11" },
+];
+
+/// worked examples of puzzle two
+const PUZZLE_TWO_EXAMPLES: [Example; 5] = [
+    Example { input: EXAMPLE_INPUTS[0], expected: "19" },
+    Example { input: EXAMPLE_INPUTS[1], expected: "23" },
+    Example { input: EXAMPLE_INPUTS[2], expected: "23" },
+    Example { input: EXAMPLE_INPUTS[3], expected: "29" },
+    Example { input: EXAMPLE_INPUTS[4], expected: "26" },
+];
+
+/// day of the advent calendar
+const DAY: u8 = 6;
+
+/// title of the day's puzzle
+const TITLE: &str = "Tuning Trouble";
+
+/// day 6 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        read_data_stream(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &PUZZLE_ONE_EXAMPLES
+    }
+}
+
+/// day 6 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        read_data_stream(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &PUZZLE_TWO_EXAMPLES
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const NUM_TEST_CASES: usize = 5;
+    use crate::error::AocError;
+    use crate::solution::{assert_examples, Solution};
 
-    //noinspection SpellCheckingInspection
-    const TEST_CASES: [&str; NUM_TEST_CASES] = [
-        "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
-        "bvwbjplbgvbhsrlpgdmjqwftvncz",
-        "nppdvjthqldpwncqszvftbrmjlhg",
-        "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg",
-        "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw",
-    ];
+    use super::find_marker_start;
 
     #[test]
-    fn puzzle_one() {
-        const EXPECTED: [&str; NUM_TEST_CASES] = ["7", "5", "6", "10", "11"];
+    fn reject_data_streams_that_arent_ascii() {
+        assert_eq!(find_marker_start("aébcdefg", 4), Some(4));
 
-        for (test_case, expected) in TEST_CASES.iter().zip(&EXPECTED) {
-            let actual = super::puzzle_one(test_case.as_bytes())
-                .expect(EXPECTED_PUZZLE_SOLUTION)
-                .to_string();
+        let Err(AocError::Parse { line, column, .. }) = super::PuzzleOne.solve(&mut "aébcdefg".as_bytes()) else {
+            panic!("expected a parse error");
+        };
 
-            assert_eq!(&actual, *expected);
-        }
+        assert_eq!((line, column), (1, 2));
     }
 
     #[test]
-    fn puzzle_two() {
-        const EXPECTED: [&str; NUM_TEST_CASES] = ["19", "23", "23", "29", "26"];
-
-        for (test_case, expected) in TEST_CASES.iter().zip(&EXPECTED) {
-            let actual = super::puzzle_two(test_case.as_bytes())
-                .expect(EXPECTED_PUZZLE_SOLUTION)
-                .to_string();
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
+    }
 
-            assert_eq!(&actual, *expected);
-        }
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
     }
-}
\ No newline at end of file
+}
@@ -1,161 +1,226 @@
 //! [AOC 2022 Day 7](https://adventofcode.com/2022/day/7)
 
-use std::{io, usize};
 use std::collections::HashMap;
-use std::ffi::OsString;
 use std::io::Read;
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use crate::utils::{CleansedLines, io_error, sum_everything};
+use nom::branch::alt;
+use nom::bytes::complete::is_not;
+use nom::character::complete::space1;
+use nom::combinator::{cut, map};
+use nom::sequence::{pair, preceded};
 
-/// Interpreted CLI session
+use crate::answer::Answer;
+use crate::error::{AocError, ParseError};
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{CleansedLines, Line, sum_everything};
+use crate::utils::parse::{delimited_pair, expect, keyword, literal, parse_line, unsigned};
+
+/// Interpreted CLI session entry
 #[allow(non_camel_case_types)]
-enum CLI {
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CLI {
+    /// `$ cd ..`, back up a number of levels, i.e. `../..` is two
     cd_back(usize),
+    /// `$ cd folder`, into a folder of the current folder
     cd_folder(String),
+    /// `$ cd /`, to the root folder
     cd_root,
+    /// `dir folder`, a folder listed in the current folder
     dir(String),
+    /// `size file`, a file and its size listed in the current folder
     file(String, usize),
+    /// `$ ls`, list the current folder
     ls,
 }
 
 impl FromStr for CLI {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let failed = |msg| format!("parsing '{input}' failed; {msg}");
-
-        let mut parts = input.split_whitespace();
-        let next = parts.next().ok_or_else(|| failed("incomplete entry"))?;
-
-        Ok(match next {
-            "$" => {
-                let next = parts.next().ok_or_else(|| failed("incomplete command entry"))?;
-
-                match next {
-                    "ls" => Self::ls,
-                    "cd" => {
-                        let next = parts.next().ok_or_else(|| failed("incomplete cd entry"))?;
-
-                        if next == "/" {
-                            Self::cd_root
-                        } else if next.starts_with("..") {
-                            Self::cd_back(next.split('/').count())
-                        } else {
-                            Self::cd_folder(next.to_string())
-                        }
-                    }
-                    _ => return Err(format!("unexpected command '{next}'"))
-                }
-            }
-            "dir" => {
-                Self::dir(parts.next().ok_or_else(|| failed("expected folder name"))?.to_string())
-            }
-            _ => {
-                Self::file(
-                    parts.next().ok_or_else(|| failed("expected file name"))?.to_string(),
-                    next.parse().map_err(|err| format!("'{input}'; {err}"))?,
-                )
-            }
-        })
+        let name = || is_not(" \t");
+
+        let folder = |folder: &str| match folder {
+            "/" => Self::cd_root,
+            back if back.starts_with("..") => Self::cd_back(back.split('/').count()),
+            folder => Self::cd_folder(folder.to_string()),
+        };
+
+        let command = preceded(
+            pair(literal("$"), space1),
+            cut(expect(
+                "expected a command, `ls` or `cd`",
+                alt((keyword("cd", map(name(), folder)), map(literal("ls"), |_| Self::ls))),
+            )),
+        );
+        let dir = keyword("dir", map(name(), |folder: &str| Self::dir(folder.to_string())));
+        let file = expect(
+            "expected a listing, `dir name` or `size name`",
+            map(delimited_pair(unsigned, " ", name()), |(size, file)| Self::file(file.to_string(), size)),
+        );
+
+        parse_line(input, alt((command, dir, file)))
     }
 }
 
 /// Interprets a CLI session log
-struct CLIInterpreter<R> {
+pub struct CLIInterpreter<R> {
     lines: CleansedLines<R>,
 }
 
 impl<R> CLIInterpreter<R>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// interprets a CLI session log
+    pub fn new(input: R) -> Self {
         Self {
             lines: CleansedLines::new(input),
         }
     }
+
+    /// line number of the last command read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.lines.line()
+    }
 }
 
 impl<R> Iterator for CLIInterpreter<R>
     where R: Read
 {
-    type Item = io::Result<CLI>;
+    type Item = Result<CLI, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let next = self.lines.next()?.ok()?;
+        let next = match self.lines.next()? {
+            Ok(next) => next,
+            Err(err) => return Some(Err(err.into())),
+        };
 
         Some(
             next.parse::<CLI>()
-                .map_err(
-                    |err| io_error(&format!("couldn't interpret '{next}' - {err}"))
-                )
+                .map_err(|err| err.in_line(DAY, self.lines.line(), &next))
         )
     }
 }
 
-// find all folders at most 100,000 bytes in size
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// find all folders at most 100,000 bytes in size
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     const THRESHOLD: RangeInclusive<usize> = 0..=100_000;
 
-    let matching_folders = rummage_drive(input)?
-        .filter_map(move |total| {
+    let drive = FileSystem::rummage(input)?;
+
+    let matching_folders = drive.folders()
+        .filter_map(move |(folder, total)| {
             if THRESHOLD.contains(&total) {
-                Some(Ok::<usize, io::Error>(total))
+                Some(Ok::<_, AocError>((format!("folder /{}", folder.display()), total)))
             } else {
                 None
             }
         });
 
-    Ok(sum_everything(matching_folders).map(Box::new)?)
+    sum_everything(DAY, matching_folders).map(Answer::from)
 }
 
-// find one folder to clear to free a minimum of 30,000,000 bytes
-// from a drive capacity of 70,000,000 bytes
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// find one folder to clear to free a minimum of 30,000,000 bytes
+/// from a drive capacity of 70,000,000 bytes
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     const TOTAL_DRIVE_SIZE: usize = 70_000_000;
     const TARGET_FREE: usize = 30_000_000;
 
-    let contents = rummage_drive(input)?.collect::<Vec<_>>();
-    let total_used = contents.iter().max().copied().unwrap_or_default();
-    let total_free = TOTAL_DRIVE_SIZE - total_used;
-    let need_to_free = TARGET_FREE - total_free;
+    let drive = FileSystem::rummage(input)?;
+    let total_used = drive.used();
+    let total_free = TOTAL_DRIVE_SIZE.checked_sub(total_used)
+        .ok_or_else(|| AocError::logic(format!("{total_used} bytes are used of a drive of {TOTAL_DRIVE_SIZE} bytes")))?;
+
+    // nothing needs to be deleted when enough is free already
+    let need_to_free = match TARGET_FREE.checked_sub(total_free) {
+        Some(need_to_free) if need_to_free > 0 => need_to_free,
+        _ => return Ok(Answer::from(0_usize)),
+    };
 
-    let solution = contents.into_iter()
+    let solution = drive.folder_sizes()
         .filter(|size| *size >= need_to_free)
         .min()
         .unwrap_or_default();
 
-    Ok(Box::new(solution))
+    Ok(Answer::from(solution))
 }
 
-/// traverse a cli session log to collect folder sizes in bytes
-fn rummage_drive<R>(input: R) -> io::Result<impl Iterator<Item=usize>>
-    where R: Read,
-{
-    let mut current = PathBuf::default();
-    let mut all_folders: HashMap<OsString, usize> = HashMap::default();
-    let cli = CLIInterpreter::new(input);
+/// Folders of a drive and the total size in bytes of everything in them,
+/// as traversed by a CLI session log; folder paths are relative to the root
+/// folder, which is the empty path
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FileSystem {
+    folders: HashMap<PathBuf, usize>,
+}
+
+impl FileSystem {
+    /// traverse a cli session log to collect folder sizes in bytes
+    ///
+    /// # Errors
+    ///
+    /// fails if the session log can't be read or interpreted, or a folder size overflows
+    pub fn rummage<R>(input: R) -> Result<Self, AocError>
+        where R: Read,
+    {
+        let mut drive = Self::default();
+        let mut current = PathBuf::default();
+        let mut commands = CLIInterpreter::new(input);
+
+        while let Some(command) = commands.next() {
+            drive.interpret(&mut current, &command?)
+                .ok_or_else(|| AocError::overflow(format!("day {DAY}, {}", Line(commands.line()))))?;
+        }
+
+        Ok(drive)
+    }
+
+    /// total size in bytes of a folder, if it was traversed
+    pub fn folder_size(&self, folder: impl AsRef<Path>) -> Option<usize> {
+        self.folders.get(folder.as_ref()).copied()
+    }
+
+    /// every folder traversed and its total size in bytes, in no particular order
+    pub fn folders(&self) -> impl Iterator<Item=(&Path, usize)> {
+        self.folders.iter().map(|(path, size)| (path.as_path(), *size))
+    }
 
-    for command in cli {
-        let command = command?;
+    /// total sizes in bytes of every folder traversed, in no particular order
+    pub fn folder_sizes(&self) -> impl Iterator<Item=usize> + '_ {
+        self.folders.values().copied()
+    }
 
-        match &command {
+    /// total size in bytes used on the drive
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.folder_sizes().max().unwrap_or_default()
+    }
+
+    /// interpret one CLI session entry, `current` tracks the current folder;
+    /// none if a file overflows the size of a folder
+    fn interpret(&mut self, current: &mut PathBuf, command: &CLI) -> Option<()> {
+        match command {
             CLI::cd_back(levels) =>
-                (0..*levels).into_iter().for_each(|_| {
+                (0..*levels).for_each(|_| {
                     current.pop();
                 }),
             CLI::cd_folder(folder) => {
                 current.push(folder);
 
-                let path = current.clone().into_os_string();
-
-                all_folders.entry(path).or_insert_with(usize::default);
+                self.folders.entry(current.clone()).or_default();
             }
             CLI::cd_root =>
                 current.clear(),
@@ -163,10 +228,9 @@ fn rummage_drive<R>(input: R) -> io::Result<impl Iterator<Item=usize>>
                 let mut current = current.clone();
 
                 loop {
-                    let path = current.as_os_str();
-                    let entry = all_folders.entry(path.to_os_string()).or_insert_with(usize::default);
+                    let folder = self.folders.entry(current.clone()).or_default();
 
-                    *entry += *size;
+                    *folder = folder.checked_add(*size)?;
 
                     if !current.pop() {
                         break;
@@ -174,26 +238,16 @@ fn rummage_drive<R>(input: R) -> io::Result<impl Iterator<Item=usize>>
                 }
             }
             CLI::dir(folder) => {
-                let mut path = current.clone();
-
-                path.push(folder);
-
-                let path = path.clone().into_os_string();
-
-                all_folders.entry(path).or_insert_with(usize::default);
+                self.folders.entry(current.join(folder)).or_default();
             }
             CLI::ls => {}
         }
-    }
 
-    Ok(all_folders.into_values())
+        Some(())
+    }
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const INPUT: &str = "$ cd /
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "$ cd /
 $ ls
 dir a
 14848514 b.txt
@@ -217,24 +271,103 @@ $ ls
 5626152 d.ext
 7214296 k";
 
+/// day of the advent calendar
+const DAY: u8 = 7;
+
+/// title of the day's puzzle
+const TITLE: &str = "No Space Left On Device";
+
+/// day 7 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        CLIInterpreter::new(input).try_for_each(|command| command.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "95437" }]
+    }
+}
+
+/// day 7 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        CLIInterpreter::new(input).try_for_each(|command| command.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "24933642" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::solution::assert_examples;
+
+    use super::{EXAMPLE_INPUT, FileSystem};
+
     #[test]
-    fn puzzle_one() {
-        let expected = "95437";
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn rummage_folder_sizes() {
+        let drive = FileSystem::rummage(EXAMPLE_INPUT.as_bytes()).expect("valid session log");
+
+        assert_eq!(drive.used(), 48_381_165);
+        assert_eq!(drive.folder_size("a"), Some(94_853));
+        assert_eq!(drive.folder_size("a/e"), Some(584));
+        assert_eq!(drive.folder_size("d"), Some(24_933_642));
+        assert_eq!(drive.folder_size("x"), None);
+        assert_eq!(drive.folders().count(), 4);
+    }
+
+    #[test]
+    fn free_space_without_deleting_or_failing_on_overfull_drives() {
+        let small = "$ cd /\n$ ls\n100 a";
+        let overfull = "$ cd /\n$ ls\n70000001 a";
 
-        assert_eq!(actual, expected);
+        assert_eq!(super::puzzle_two(small.as_bytes()).map(|answer| answer.to_string()).ok(), Some(String::from("0")));
+        assert!(matches!(super::puzzle_two(overfull.as_bytes()), Err(AocError::Logic(_))));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "24933642";
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn fail_when_a_folder_size_overflows() {
+        let input = format!("$ cd /\n$ ls\n{} a\n1 b", usize::MAX);
 
-        assert_eq!(actual, expected);
+        match FileSystem::rummage(input.as_bytes()) {
+            Err(AocError::Overflow(record)) => assert_eq!(record, "day 7, line 4"),
+            other => panic!("expected an overflow, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
     }
-}
 
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
+    }
+}
@@ -1,149 +1,158 @@
 //! [AOC 2022 Day 8](https://adventofcode.com/2022/day/8)
 
-use std::io;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 use std::ops::ControlFlow;
 
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{Direction, Grid};
+
 /// find all visible trees from outside the grid
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    let (width, height, trees) = parse_forrest(input)?;
-    let mut hidden = Vec::new();
-
-    for y in 1..height - 1 {
-        for x in 1..width - 1 {
-            let tree = trees.get(x + y * width).unwrap();
-
-            if (0..y).any(|y| trees.get(x + y * width).unwrap() >= tree) &&
-                (y + 1..height).any(|y| trees.get(x + y * width).unwrap() >= tree) &&
-                (0..x).any(|x| trees.get(x + y * width).unwrap() >= tree) &&
-                (x + 1..width).any(|x| trees.get(x + y * width).unwrap() >= tree) {
-                hidden.push(((x, y), *tree));
-            }
-        }
-    }
+    let forrest = parse_forrest(input)?;
 
-    Ok(Box::new(width * height - hidden.len()))
+    // a tree is visible if every tree towards any edge is shorter
+    let visible = forrest.cells()
+        .filter(|((x, y), tree)| Direction::ALL.into_iter()
+            .any(|direction| forrest.ray(*x, *y, direction).all(|(_, other)| other < tree))
+        )
+        .count();
+
+    Ok(Answer::from(visible))
 }
 
 /// finds highest scenic score possible for any tree
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    let (width, height, forrest) = parse_forrest(input)?;
-    let mut most_scenic = 0;
-
-    for y in 1..height - 1 {
-        for x in 1..width - 1 {
-            let candidate = forrest.get(x + y * width).unwrap();
-
-            let north = (0..y).map(|y1| x + (y - 1 - y1) * width);
-            let north = gaze_upon_yonder(north, *candidate, &forrest);
+    let forrest = parse_forrest(input)?;
 
-            let south = (y + 1..height).map(|y| x + y * width);
-            let south = gaze_upon_yonder(south, *candidate, &forrest);
+    let most_scenic = forrest.cells()
+        .map(|((x, y), candidate)| Direction::ALL.into_iter()
+            .map(|direction| gaze_upon_yonder(forrest.ray(x, y, direction), *candidate))
+            .product::<usize>()
+        )
+        .max()
+        .unwrap_or_default();
 
-            let east = (x + 1..width).map(|x| x + y * width);
-            let east = gaze_upon_yonder(east, *candidate, &forrest);
-
-            let west = (0..x).map(|x1| (x - 1 - x1) + y * width);
-            let west = gaze_upon_yonder(west, *candidate, &forrest);
-
-            let rank = north * south * west * east;
-
-            most_scenic = most_scenic.max(rank);
-        }
-    }
-
-    Ok(Box::new(most_scenic))
+    Ok(Answer::from(most_scenic))
 }
 
 /// calculates the scenic score of a single gaze direction
-fn gaze_upon_yonder(
-    mut yonder: impl Iterator<Item=usize>,
+fn gaze_upon_yonder<'a>(
+    mut yonder: impl Iterator<Item=((usize, usize), &'a u8)>,
     candidate: u8,
-    forrest: &[u8],
 ) -> usize {
     let score = yonder
-        .try_fold(0, |score, tree_in_forrest| {
-            let checked_tree = *forrest.get(tree_in_forrest).unwrap();
-
-            if checked_tree >= candidate {
+        .try_fold(0, |score, (_, checked_tree)| {
+            if *checked_tree >= candidate {
                 ControlFlow::Break(score + 1)
             } else {
                 ControlFlow::Continue(score + 1)
             }
         });
 
-    let score = match score {
+    match score {
         ControlFlow::Continue(score) |
         ControlFlow::Break(score) => score
-    };
-
-    score.max(1)
+    }
 }
 
-/// parses an input file of s planted forrest and it's width & height
-fn parse_forrest<R>(input: R) -> io::Result<(usize, usize, Vec<u8>)>
+/// parses an input file of a planted forrest, a tree height for each digit
+///
+/// # Errors
+///
+/// fails if the input can't be read, a tree height isn't a digit or rows of trees aren't the same width
+pub fn parse_forrest<R>(input: R) -> Result<Grid<u8>, AocError>
     where R: Read
 {
-    let mut width = 0;
-    let mut height = 0;
-    let zero = b'0';
-
-    let trees = BufReader::new(input)
-        .lines()
-        .try_fold(
-            Vec::new(),
-            |mut forrest, row| {
-                let mut row = match row {
-                    Ok(row) => row.chars().map(|c| c as u8 - zero).collect::<Vec<_>>(),
-                    Err(_) => return ControlFlow::Break(row)
-                };
-
-                width = width.max(row.len());
-                height += 1;
-
-                forrest.append(&mut row);
-
-                ControlFlow::Continue(forrest)
-            });
-
-    match trees {
-        ControlFlow::Continue(trees) => Ok((width, height, trees)),
-        ControlFlow::Break(Err(err)) => Err(err),
-        ControlFlow::Break(Ok(_)) => unreachable!()
-    }
+    Grid::parse(input, DAY, |tree| tree.to_digit(10)
+        .and_then(|height| u8::try_from(height).ok())
+        .ok_or_else(|| format!("'{tree}' is not a tree height"))
+    )
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const INPUT: &str = "30373
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "30373
 25512
 65332
 33549
 35390";
 
+/// day of the advent calendar
+const DAY: u8 = 8;
+
+/// title of the day's puzzle
+const TITLE: &str = "Treetop Tree House";
+
+/// day 8 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_forrest(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "21" }]
+    }
+}
+
+/// day 8 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_forrest(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "8" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::assert_examples;
+
     #[test]
     fn puzzle_one() {
-        let expected = "21";
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
-
-        assert_eq!(actual, expected);
+        assert_examples(&super::PuzzleOne);
     }
 
     #[test]
     fn puzzle_two() {
-        let expected = "8";
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
-
-        assert_eq!(actual, expected);
+        assert_examples(&super::PuzzleTwo);
     }
 }
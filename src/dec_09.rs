@@ -1,42 +1,60 @@
 //! [AOC 2022 Day 9](https://adventofcode.com/2022/day/9)
 
-use std::{fmt, io};
+use std::fmt;
 use std::collections::{HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
 use std::io::Read;
 use std::ops::{Add, Deref};
 use std::str::FromStr;
 
-use crate::utils::{CleansedLines, io_error};
+use nom::character::complete::one_of;
+
+use crate::answer::Answer;
+use crate::error::{AocError, ParseError};
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{CleansedLines, Grid};
+use crate::utils::parse::{delimited_pair, expect, parse_line, unsigned};
 
 const TRACE_SOLUTION: bool = true;
 
-/// Iterator of head movements
-struct FollowYourHead<R> {
+/// Iterator of head locations, one step at a time, starting at the origin
+pub struct FollowYourHead<R> {
     /// input stream of head movements
     lines: CleansedLines<R>,
     /// current location of head
     current: Option<Location>,
     /// step iterator of current head movement
     steps: Option<Box<dyn Iterator<Item=Movement>>>,
+    /// strict or lenient about invalid head movements
+    mode: Mode,
+    /// number of invalid head movements skipped
+    skipped: usize,
 }
 
 impl<R> FollowYourHead<R>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// follow the head movements of an input, strictly or skipping invalid movements
+    pub fn new(input: R, mode: Mode) -> Self {
         Self {
             lines: CleansedLines::new(input),
             current: None,
             steps: None,
+            mode,
+            skipped: 0,
         }
     }
+
+    /// number of invalid head movements skipped so far
+    pub const fn skipped(&self) -> usize {
+        self.skipped
+    }
 }
 
 impl<R> Iterator for FollowYourHead<R>
     where R: Read
 {
-    type Item = io::Result<Location>;
+    type Item = Result<Location, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         // at start; initialize
@@ -52,8 +70,20 @@ impl<R> Iterator for FollowYourHead<R>
         loop {
             // if no more steps, get next head movement
             if self.steps.is_none() {
-                let next = self.lines.next()?.ok()?;
-                let next_steps = parse_move(&next).ok()?;
+                let next = match self.lines.next()? {
+                    Ok(next) => next,
+                    Err(err) => return Some(Err(err.into())),
+                };
+                let next_steps = match next.parse::<Movement>() {
+                    Ok(steps) => steps,
+                    // skip invalid movements when lenient
+                    Err(_) if self.mode == Mode::Lenient => {
+                        self.skipped += 1;
+
+                        continue;
+                    }
+                    Err(err) => return Some(Err(err.in_line(DAY, self.lines.line(), &next))),
+                };
 
                 // setup step iterator
                 self.steps = Some(Box::new(next_steps));
@@ -79,30 +109,26 @@ impl<R> Iterator for FollowYourHead<R>
         // update head current location
         self.current = Some(current);
 
-        return Some(Ok(current));
-
-        // parse steps instruction input, result err match iterator Item
-        fn parse_move(input: &str) -> Result<Movement, Option<io::Result<Location>>> {
-            match input.parse::<Movement>() {
-                Ok(steps) => Ok(steps),
-                Err(err) => Err(Some(Err(io_error(&err))))
-            }
-        }
+        Some(Ok(current))
     }
 }
 
 /// represents a size value of height and width
 #[derive(Copy, Clone, Debug, Default)]
-struct Size {
-    height: usize,
-    width: usize,
+pub struct Size {
+    /// height
+    pub height: usize,
+    /// width
+    pub width: usize,
 }
 
 /// represents a location x, y
-#[derive(Copy, Clone, Default, Hash, Eq, PartialEq)]
-struct Location {
-    x: isize,
-    y: isize,
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Location {
+    /// horizontal position, increasing to the right
+    pub x: isize,
+    /// vertical position, increasing upwards
+    pub y: isize,
 }
 
 impl Display for Location {
@@ -113,9 +139,11 @@ impl Display for Location {
 
 /// represents a movement vector
 #[derive(Copy, Clone, Debug, Default)]
-struct Move {
-    x: isize,
-    y: isize,
+pub struct Move {
+    /// horizontal movement
+    pub x: isize,
+    /// vertical movement
+    pub y: isize,
 }
 
 /// add operator for adding a move vector to location
@@ -129,30 +157,34 @@ impl Add<&Move> for Location {
 
 /// Grid setup information
 #[derive(Copy, Clone, Default)]
-struct GridSetup {
+pub struct GridSetup {
     /// size of grid
-    size: Size,
+    pub size: Size,
     /// start location for grid
-    start: Option<Location>,
+    pub start: Option<Location>,
 }
 
 impl GridSetup {
     /// return a start location, chooses the center if one is not provided
     #[allow(clippy::cast_possible_wrap)]
-    fn start(&mut self) -> Location {
-        if self.start.is_none() {
-            self.start = Some(Location {
-                x: (self.size.width >> 1) as isize,
-                y: (self.size.height >> 1) as isize,
-            });
+    pub const fn start(&mut self) -> Location {
+        if let Some(start) = self.start {
+            return start;
         }
 
-        self.start.expect("expect a start location is defined")
+        let start = Location {
+            x: (self.size.width >> 1) as isize,
+            y: (self.size.height >> 1) as isize,
+        };
+
+        self.start = Some(start);
+
+        start
     }
 }
 
-/// Iterator of final tail knot movements
-struct FollowYourTail<R> {
+/// Iterator of final tail knot locations
+pub struct FollowYourTail<R> {
     follow: FollowYourHead<R>,
     grid_setup: Option<GridSetup>,
     knots: Vec<Location>,
@@ -162,7 +194,9 @@ struct FollowYourTail<R> {
 impl<R> FollowYourTail<R>
     where R: Read
 {
-    fn new(input: R, knots: usize, grid_setup: Option<GridSetup>, trace: bool) -> Self {
+    /// follow the last of a number of tail knots, at most 9, behind the head movements of an input;
+    /// optionally drawing each step on a grid and tracing knot locations, for debugging
+    pub fn new(input: R, knots: usize, grid_setup: Option<GridSetup>, trace: bool, mode: Mode) -> Self {
         let knots = knots.min(9);
         let knots_range = 0..=knots;
         let start = Location::default();
@@ -171,18 +205,23 @@ impl<R> FollowYourTail<R>
         knots_range.for_each(|_| knots.push(start));
 
         Self {
-            follow: FollowYourHead::new(input),
+            follow: FollowYourHead::new(input, mode),
             grid_setup,
             knots,
             trace,
         }
     }
+
+    /// number of invalid head movements skipped so far
+    pub const fn skipped(&self) -> usize {
+        self.follow.skipped()
+    }
 }
 
 impl<R> Iterator for FollowYourTail<R>
     where R: Read
 {
-    type Item = io::Result<Location>;
+    type Item = Result<Location, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         const HEAD_TAIL: &str = "HT";
@@ -202,13 +241,16 @@ impl<R> Iterator for FollowYourTail<R>
         };
 
         // first gets where the head's next movement is
-        let mut next = self.follow.next()?.ok()?;
+        let mut next = match self.follow.next()? {
+            Ok(next) => next,
+            Err(err) => return Some(Err(err)),
+        };
         // define range of knots to iterate, one less than total knots
         let knots_range = 0..self.knots.len() - 1;
         let knots = self.knots.as_mut_slice();
 
         // optionally setup of a path grid and symbols tracker
-        let mut path = self.grid_setup.map(Grid::new);
+        let mut path = self.grid_setup.map(Trail::new);
         // symbols need to be tracked in the opposite order than they are processed
         let mut symbols = self.grid_setup.map(|_| VecDeque::new());
 
@@ -260,7 +302,7 @@ impl<R> Iterator for FollowYourTail<R>
         });
 
         if let Some(path) = path {
-            println!("\n{}\n", path.to_string());
+            println!("\n{path}\n");
         }
 
         // get the visited location of last knot
@@ -328,24 +370,22 @@ impl Movement {
 
 /// parse movement instruction from string
 impl FromStr for Movement {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(src: &str) -> Result<Self, Self::Err> {
-        let (dir, steps) = src.split_once(' ')
-            .ok_or_else(|| format!("'{src} is not a valid move"))?;
-
-        let steps = steps.trim()
-            .parse::<usize>()
-            .map_err(|err| format!(
-                "'{}' is not a valid steps value - {err}", steps.trim()
-            ))? + 1;
-
-        Ok(match dir.trim() {
-            "D" | "d" => Self::Down(steps),
-            "L" | "l" => Self::Left(steps),
-            "R" | "r" => Self::Right(steps),
-            "U" | "u" => Self::Up(steps),
-            _ => return Err(format!("'{}' is not a valid direction", dir.trim())),
+        let direction = expect("expected a direction, `D`, `L`, `R` or `U`", one_of("DLRUdlru"));
+        let (dir, steps) = parse_line(src, delimited_pair(direction, " ", unsigned::<usize>))?;
+        let count = steps.checked_add(1).ok_or_else(|| {
+            let column = src.find(|step: char| step.is_ascii_digit()).map_or(1, |offset| offset + 1);
+
+            ParseError::new(column, format!("{steps} steps are more than can be taken"))
+        })?;
+
+        Ok(match dir.to_ascii_uppercase() {
+            'D' => Self::Down(count),
+            'L' => Self::Left(count),
+            'R' => Self::Right(count),
+            _ => Self::Up(count),
         })
     }
 }
@@ -357,21 +397,9 @@ impl Iterator for Movement {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self {
-            Self::Down(steps) if *steps > 1 => {
-                *steps -= 1;
-
-                Some(*self)
-            },
-            Self::Left(steps) if *steps > 1 => {
-                *steps -= 1;
-
-                Some(*self)
-            },
-            Self::Right(steps) if *steps > 1 => {
-                *steps -= 1;
-
-                Some(*self)
-            },
+            Self::Down(steps) |
+            Self::Left(steps) |
+            Self::Right(steps) |
             Self::Up(steps) if *steps > 1 => {
                 *steps -= 1;
 
@@ -398,36 +426,28 @@ impl Deref for Movement {
 }
 
 /// display grid for current state of calculation, for debugging
-struct Grid {
-    grid: Vec<u8>,
+struct Trail {
+    cells: Grid<char>,
     start: Location,
-    width: isize,
 }
 
-impl Grid {
-    #[allow(clippy::cast_possible_wrap)]
+impl Trail {
+    /// an empty cell
+    const EMPTY: char = '.';
+    /// the start location, when nothing else is on it
+    const START: char = 's';
+    /// a location visited by the last knot
+    const VISITED: char = '#';
+
     fn new(mut grid_setup: GridSetup) -> Self {
         let size = grid_setup.size;
-        // initial empty grid
-        let grid = (0..size.height).map(|_| ".".repeat(size.width)).collect::<Vec<_>>().join("\n");
-        let start = grid_setup.start();
-        let width = size.width + 1;
 
         Self {
-            grid: grid.into_bytes(),
-            start,
-            width: width as isize,
+            cells: Grid::filled(size.width, size.height, Self::EMPTY),
+            start: grid_setup.start(),
         }
     }
-}
-
-impl ToString for Grid {
-    fn to_string(&self) -> String {
-        String::from_utf8_lossy(&self.grid).to_string()
-    }
-}
 
-impl Grid {
     /// update a next location w/a symbol
     fn update(
         &mut self,
@@ -435,28 +455,24 @@ impl Grid {
         next: &Location,
         symbol: char,
     ) {
-        // transpose prior location relative to start and get index into grid
-        let idx = self.index(&self.transpose(prior));
-        let symbol = symbol as u8;
-
-        // if index has same value, erase it
-        if self.grid[idx] == symbol {
-            self.grid[idx] = b'.';
+        // if prior location has same symbol, erase it
+        if let Some((x, y)) = Self::cell(self.transpose(prior)) {
+            if self.cells.get(x, y) == Some(&symbol) {
+                self.cells.set(x, y, Self::EMPTY);
+            }
         }
 
-        // get index of start location
-        let start = self.index(&self.start);
-
-        // if index is blank or has been erased, draw start indicator
-        if self.grid[start] == b'.' {
-            self.grid[start] = b's';
+        // if start location is blank or has been erased, draw start indicator
+        if let Some((x, y)) = Self::cell(self.start) {
+            if self.cells.get(x, y) == Some(&Self::EMPTY) {
+                self.cells.set(x, y, Self::START);
+            }
         }
 
-        // transpose next location relative to start and get index into grid
-        let idx = self.index(&self.transpose(next));
-
-        // draw symbol
-        self.grid[idx] = symbol;
+        // draw symbol at next location, relative to start
+        if let Some((x, y)) = Self::cell(self.transpose(next)) {
+            self.cells.set(x, y, symbol);
+        }
     }
 
     /// draw visited locations to grid
@@ -464,9 +480,9 @@ impl Grid {
     fn visited(&mut self, visited: impl Iterator<Item=Location>)
     {
         for location in visited {
-            let idx = self.index(&self.transpose(&location));
-
-            self.grid[idx] = b'#';
+            if let Some((x, y)) = Self::cell(self.transpose(&location)) {
+                self.cells.set(x, y, Self::VISITED);
+            }
         }
     }
 
@@ -475,25 +491,62 @@ impl Grid {
         Location { x: location.x + self.start.x, y: self.start.y - location.y }
     }
 
-    /// get the index of a flat grid representation from a 2d location
-    #[allow(clippy::cast_sign_loss)]
-    const fn index(&self, location: &Location) -> usize {
-        (location.x + location.y * self.width) as usize
+    /// the grid cell of a transposed location, `None` if it is left of or above the grid
+    fn cell(location: Location) -> Option<(usize, usize)> {
+        Some((usize::try_from(location.x).ok()?, usize::try_from(location.y).ok()?))
+    }
+}
+
+impl Display for Trail {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", self.cells)
     }
 }
 
 /// solution for all locations visited by one knot
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    puzzle_do(input, 1, None, !TRACE_SOLUTION)
+    puzzle_do(input, 1, None, !TRACE_SOLUTION, Mode::Strict).map(|(answer, _)| answer)
 }
 
 /// solution for all locations visited by last knot of nine knots
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    puzzle_do(input, 9, None, !TRACE_SOLUTION)
+    puzzle_do(input, 9, None, !TRACE_SOLUTION, Mode::Strict).map(|(answer, _)| answer)
+}
+
+/// parses the head movements of an input, strictly or skipping invalid movements,
+/// answering the number of invalid movements skipped
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or, when strict, a movement isn't valid
+pub fn parse_movements<R>(input: R, mode: Mode) -> Result<usize, AocError>
+    where R: Read
+{
+    let mut skipped = 0;
+
+    for numbered in CleansedLines::new(input).numbered() {
+        let (line, movement) = numbered?;
+
+        match movement.parse::<Movement>() {
+            Ok(_) => {}
+            Err(_) if mode == Mode::Lenient => skipped += 1,
+            Err(err) => return Err(err.in_line(DAY, line, &movement)),
+        }
+    }
+
+    Ok(skipped)
 }
 
 fn puzzle_do<R>(
@@ -501,31 +554,27 @@ fn puzzle_do<R>(
     knots: usize,
     grid_setup: Option<GridSetup>,
     trace: bool,
-) -> io::Result<Box<dyn ToString>>
+    mode: Mode,
+) -> Result<(Answer, usize), AocError>
     where R: Read
 {
-    let visited = FollowYourTail::new(input, knots, grid_setup, trace)
-        .collect::<Result<HashSet<_>, _>>()?;
+    let mut tail = FollowYourTail::new(input, knots, grid_setup, trace, mode);
+    let visited = tail.by_ref().collect::<Result<HashSet<_>, _>>()?;
     let solution = visited.len();
 
     if let Some(grid_setup) = grid_setup {
-        let mut path = Grid::new(grid_setup);
+        let mut path = Trail::new(grid_setup);
 
         path.visited(visited.into_iter());
 
-        println!("\n{}\n", path.to_string());
+        println!("\n{path}\n");
     }
 
-    Ok(Box::new(solution))
+    Ok((Answer::from(solution), tail.skipped()))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    #[test]
-    fn puzzle_one() {
-        const INPUT: &str = "R 4
+/// worked example of puzzle one from the puzzle text
+const PUZZLE_ONE_EXAMPLE: &str = "R 4
 U 4
 L 3
 D 1
@@ -534,50 +583,137 @@ D 1
 L 5
 R 2";
 
-        let expected = "13";
+/// larger worked example of puzzle two from the puzzle text
+const PUZZLE_TWO_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+/// day of the advent calendar
+const DAY: u8 = 9;
+
+/// title of the day's puzzle
+const TITLE: &str = "Rope Bridge";
+
+/// day 9 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn solve_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(Answer, usize), AocError> {
+        puzzle_do(input, 1, None, !TRACE_SOLUTION, mode)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(), AocError> {
+        parse_movements(input, mode).map(drop)
+    }
 
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: PUZZLE_ONE_EXAMPLE, expected: "13" }]
+    }
+}
+
+/// day 9 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn solve_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(Answer, usize), AocError> {
+        puzzle_do(input, 9, None, !TRACE_SOLUTION, mode)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(), AocError> {
+        parse_movements(input, mode).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: PUZZLE_TWO_EXAMPLE, expected: "36" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::solution::{assert_examples, Mode, Solution};
+
+    use super::{PUZZLE_ONE_EXAMPLE, PuzzleOne};
+
+    #[test]
+    fn skip_invalid_movements_only_when_lenient() {
+        let input = PUZZLE_ONE_EXAMPLE.replace("L 3", "L three");
+        let skipped = PUZZLE_ONE_EXAMPLE.replace("L 3\n", "");
+
+        let Err(AocError::Parse { line, column, .. }) = PuzzleOne.solve_in(&mut input.as_bytes(), Mode::Strict) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (3, 3));
+
+        let lenient = PuzzleOne.solve_in(&mut input.as_bytes(), Mode::Lenient).expect("skipped invalid movement");
+        let expected = PuzzleOne.solve(&mut skipped.as_bytes()).expect(crate::EXPECTED_PUZZLE_SOLUTION);
+
+        assert_eq!(lenient, (expected, 1));
+
+        // a step too many to count is invalid too
+        let input = PUZZLE_ONE_EXAMPLE.replace("L 3", &format!("L {}", usize::MAX));
+
+        let Err(AocError::Parse { line, column, .. }) = PuzzleOne.solve_in(&mut input.as_bytes(), Mode::Strict) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (3, 3));
+        assert_eq!(PuzzleOne.solve_in(&mut input.as_bytes(), Mode::Lenient).ok(), Some(lenient));
+    }
+
+    #[test]
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
 
         // let grid_setup = super::GridSetup {
         //     size: super::Size { height: 5, width: 7 },
         //     start: Some(super::Location { x: 0, y: 4 }),
         // };
         //
-        // let actual = super::puzzle_do(INPUT.as_bytes(), 1, Some(grid_setup), !super::TRACE_SOLUTION)
-        //     .expect(EXPECTED_PUZZLE_SOLUTION)
+        // let actual = super::puzzle_do(super::PUZZLE_ONE_EXAMPLE.as_bytes(), 1, Some(grid_setup), !super::TRACE_SOLUTION, super::Mode::Strict)
+        //     .expect(crate::EXPECTED_PUZZLE_SOLUTION)
+        //     .0
         //     .to_string();
-
-        assert_eq!(actual, expected);
     }
 
     #[test]
     fn puzzle_two() {
-        const INPUT: &str = "R 5
-U 8
-L 8
-D 3
-R 17
-D 10
-L 25
-U 20";
-
-        let expected = "36";
-
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+        assert_examples(&super::PuzzleTwo);
 
         // let grid_setup = super::GridSetup {
         //     size: super::Size { height: 30, width: 30 },
         //     start: None,
         // };
         //
-        // let actual = super::puzzle_do(INPUT.as_bytes(), 9, Some(grid_setup), super::TRACE_SOLUTION)
-        //     .expect(EXPECTED_PUZZLE_SOLUTION)
+        // let actual = super::puzzle_do(super::PUZZLE_TWO_EXAMPLE.as_bytes(), 9, Some(grid_setup), super::TRACE_SOLUTION, super::Mode::Strict)
+        //     .expect(crate::EXPECTED_PUZZLE_SOLUTION)
+        //     .0
         //     .to_string();
-
-        assert_eq!(actual, expected);
     }
 }
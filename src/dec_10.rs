@@ -1,51 +1,96 @@
 //! [AOC 2022 Day 10](https://adventofcode.com/2022/day/10)
 
-use std::io;
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::io::Read;
+use std::iter;
 use std::ops::Deref;
 use std::str::FromStr;
 
-use crate::utils::{CleansedLines, io_error, sum_everything};
+use nom::branch::alt;
+use nom::bytes::complete::tag_no_case;
+use nom::character::complete::{space0, space1};
+use nom::combinator::{cut, map};
+use nom::sequence::{pair, preceded};
+
+use crate::answer::{Answer, Raster};
+use crate::error::{AocError, ParseError};
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::{CleansedLines, Grid, Line, sum_everything};
+use crate::utils::parse::{expect, parse_line, signed};
 
 /// iterator of cpu instructions
-struct Instructions<R> {
+pub struct Instructions<R> {
     lines: CleansedLines<R>,
+    mode: Mode,
+    skipped: usize,
 }
 
 impl<R> Instructions<R>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// iterate the cpu instructions of an input, strictly or skipping invalid instructions
+    pub fn new(input: R, mode: Mode) -> Self {
         Self {
             lines: CleansedLines::new(input),
+            mode,
+            skipped: 0,
         }
     }
+
+    /// number of invalid instructions skipped so far
+    pub const fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// line number of the last instruction read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.lines.line()
+    }
 }
 
 impl<R> Iterator for Instructions<R>
     where R: Read
 {
-    type Item = io::Result<Instruction>;
+    type Item = Result<Instruction, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // get next instruction
-        let next = self.lines.next()?.ok()?;
-
-        // parse instruction
-        Some(next.parse::<Instruction>().
-            map_err(|err| io_error(&err.to_string())))
+        loop {
+            // get next instruction
+            let next = match self.lines.next()? {
+                Ok(next) => next,
+                Err(err) => return Some(Err(err.into())),
+            };
+
+            // parse instruction
+            match next.parse::<Instruction>() {
+                Ok(instruction) => return Some(Ok(instruction)),
+                // skip invalid instructions when lenient
+                Err(_) if self.mode == Mode::Lenient => self.skipped += 1,
+                Err(err) => return Some(Err(err.in_line(DAY, self.lines.line(), &next))),
+            }
+        }
     }
 }
 
-// simple cpu with two instructions
-struct CPU {
+/// simple cpu with two instructions
+#[derive(Debug)]
+pub struct CPU {
     cycles: usize,
     current_op: Option<Instruction>,
     register_x: isize,
 }
 
+impl Default for CPU {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CPU {
-    const fn new() -> Self {
+    /// a cpu at its first cycle, with a `register_x` of 1
+    #[must_use]
+    pub const fn new() -> Self {
         Self {
             cycles: 1,
             current_op: None,
@@ -53,46 +98,74 @@ impl CPU {
         }
     }
 
-    fn run_instructions<R>(&mut self, instructions: Instructions<R>) -> Cycles<R>
+    /// current cycle of the cpu
+    #[must_use]
+    pub const fn cycle(&self) -> usize {
+        self.cycles
+    }
+
+    /// current value of the cpu's `register_x`
+    #[must_use]
+    pub const fn register_x(&self) -> isize {
+        self.register_x
+    }
+
+    /// run instructions on the cpu, one cycle at a time
+    pub const fn run_instructions<R>(&mut self, instructions: Instructions<R>) -> Cycles<'_, R>
         where R: Read
     {
         Cycles::new(self, instructions)
     }
 }
 
-/// iterator of `cpu` `instruction` cycles
-struct Cycles<'a, R> {
+/// iterator of `cpu` `instruction` cycles, yielding the `cycle` and
+/// the `register_x` during the cycle
+pub struct Cycles<'a, R> {
     cpu: &'a mut CPU,
-    cycles: usize,
+    remaining: usize,
     instructions: Instructions<R>,
 }
 
 impl<'a, R> Cycles<'a, R>
     where R: Read
 {
-    fn new(cpu: &'a mut CPU, instructions: Instructions<R>) -> Self {
+    /// run instructions on a cpu
+    pub const fn new(cpu: &'a mut CPU, instructions: Instructions<R>) -> Self {
         Self {
             cpu,
-            cycles: 0,
+            remaining: 0,
             instructions,
         }
     }
+
+    /// number of invalid instructions skipped so far
+    pub const fn skipped(&self) -> usize {
+        self.instructions.skipped()
+    }
+
+    /// line number of the instruction executing, starting at 1
+    pub const fn line(&self) -> usize {
+        self.instructions.line()
+    }
 }
 
-impl<'a, R> Iterator for Cycles<'a, R>
+impl<R> Iterator for Cycles<'_, R>
     where R: Read
 {
-    type Item = io::Result<(usize, isize)>;
+    type Item = Result<(usize, isize), AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             // check if cpu is executing an instruction
             if self.cpu.current_op.is_none() {
                 // get next instruction
-                let next = self.instructions.next()?.ok()?;
+                let next = match self.instructions.next()? {
+                    Ok(next) => next,
+                    Err(err) => return Some(Err(err)),
+                };
 
                 // setup cycles and cpu
-                self.cycles = *next;
+                self.remaining = *next;
                 self.cpu.current_op = Some(next);
             } else {
                 // current state of cpu
@@ -101,14 +174,18 @@ impl<'a, R> Iterator for Cycles<'a, R>
                 // update next cpu cycle
                 self.cpu.cycles += 1;
                 // update current instruction cycle
-                self.cycles -= 1;
+                self.remaining -= 1;
 
                 // check if current instruction is complete
-                if self.cycles == 0 {
+                if self.remaining == 0 {
                     // if current instruction is addx
                     if let Some(Instruction::AddX(value)) = self.cpu.current_op {
                         // update register with addx operand
-                        self.cpu.register_x += value;
+                        let Some(register_x) = self.cpu.register_x.checked_add(value) else {
+                            return Some(Err(AocError::overflow(format!("day {DAY}, {}", Line(self.line())))));
+                        };
+
+                        self.cpu.register_x = register_x;
                     }
 
                     // mark current operation complete
@@ -123,9 +200,11 @@ impl<'a, R> Iterator for Cycles<'a, R>
 }
 
 /// `cpu` instructions
-#[derive(Copy, Clone, Debug)]
-enum Instruction {
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Instruction {
+    /// does nothing for a cycle
     Noop,
+    /// adds a value to `register_x` after two cycles
     AddX(isize),
 }
 
@@ -143,30 +222,19 @@ impl Deref for Instruction {
 
 /// parse a `cpu` `instruction` from a `str`
 impl FromStr for Instruction {
-    type Err = io::Error;
-
-    fn from_str(source: &str) -> Result<Self, Self::Err> {
-        let source = source.trim();
+    type Err = ParseError;
 
-        if source.eq_ignore_ascii_case("noop") {
-            Ok(Self::Noop)
-        } else if let Some((op, value)) = source.split_once(' ') {
-            if op.trim().eq_ignore_ascii_case("addx") {
-                let value = value.parse::<isize>()
-                    .map_err(|err| io_error(&format!("'{value}' is not a valid value for `addx` instruction; {err}")))?;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let noop = map(tag_no_case("noop"), |_| Self::Noop);
+        let addx = map(preceded(pair(tag_no_case("addx"), space1), cut(signed)), Self::AddX);
 
-                Ok(Self::AddX(value))
-            } else {
-                Err(io_error(&format!("'{op}' is not a valid instruction")))
-            }
-        } else {
-            Err(io_error(&format!("'{source}' is not a valid instruction")))
-        }
+        parse_line(line, preceded(space0, expect("expected an instruction, `noop` or `addx`", alt((noop, addx)))))
     }
 }
 
-/// iterator of signals processed from `cpu`
-struct SignalProcessor<'a, R> {
+/// iterator of signals processed from `cpu`, the signal strength of every
+/// `trigger_freq` cycles starting at cycle `trigger_offset`
+pub struct SignalProcessor<'a, R> {
     cycles: Cycles<'a, R>,
     trigger_freq: usize,
     trigger_offset: usize,
@@ -175,55 +243,82 @@ struct SignalProcessor<'a, R> {
 impl<'a, R> SignalProcessor<'a, R>
     where R: Read
 {
-    const fn new(cycles: Cycles<'a, R>, trigger_offset: usize, trigger_freq: usize) -> Self {
+    /// process the signals of cpu cycles
+    pub const fn new(cycles: Cycles<'a, R>, trigger_offset: usize, trigger_freq: usize) -> Self {
         Self { cycles, trigger_freq, trigger_offset }
     }
+
+    /// number of invalid instructions skipped so far
+    pub const fn skipped(&self) -> usize {
+        self.cycles.skipped()
+    }
+
+    /// line number of the instruction executing, starting at 1
+    pub const fn line(&self) -> usize {
+        self.cycles.line()
+    }
 }
 
-impl<'a, R> Iterator for SignalProcessor<'a, R>
+impl<R> Iterator for SignalProcessor<'_, R>
     where R: Read,
 {
-    type Item = io::Result<isize>;
+    type Item = Result<isize, AocError>;
 
     #[allow(clippy::cast_possible_wrap)]
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            let (cycle, register_x) = self.cycles.next()?.ok()?;
+            let (cycle, register_x) = match self.cycles.next()? {
+                Ok(cycle) => cycle,
+                Err(err) => return Some(Err(err)),
+            };
 
             // process signal based on input parameters
-            if cycle >= self.trigger_offset && 
-                (cycle - self.trigger_offset) % self.trigger_freq == 0 
+            if cycle >= self.trigger_offset &&
+                (cycle - self.trigger_offset).is_multiple_of(self.trigger_freq)
             {
-                return Some(Ok(cycle as isize *  register_x));
+                return Some((cycle as isize).checked_mul(register_x)
+                    .ok_or_else(|| AocError::overflow(format!("day {DAY}, {}", Line(self.cycles.line())))));
             }
         }
     }
 }
 
 /// `CRT` that displays frames from a signal processor
-struct CRT {
-    pixels: Vec<u8>,
+#[derive(Debug)]
+pub struct CRT {
+    frame: Grid<bool>,
     size: Size,
 }
 
 impl CRT {
-    fn new(size: Size) -> Self {
-        // draw an empty frame of pixels
-        let pixels = (0..size.height)
-            .map(|_| ".".repeat(size.width))
-            .collect::<Vec<_>>()
-            .join("\n")
-            .into_bytes();
-
+    /// a crt of a size, with a blank frame
+    #[must_use]
+    pub fn new(size: Size) -> Self {
         Self {
-            pixels,
+            frame: Grid::new(size.width, size.height),
             size,
         }
     }
 
-    // updates the `crt` frame from input signals of `cpu`'s `cycle` and `register_x`
+    /// the current frame of the crt, lit pixels are `true`
+    #[must_use]
+    pub const fn pixels(&self) -> &Grid<bool> {
+        &self.frame
+    }
+
+    /// the current frame of the crt as an image
+    #[must_use]
+    pub fn frame(&self) -> Raster {
+        Raster::from(self.frame.clone())
+    }
+
+    /// updates the `crt` frame from input signals of `cpu`'s `cycle` and `register_x`
+    ///
+    /// # Errors
+    ///
+    /// the first error of the signals
     #[allow(clippy::cast_possible_wrap)]
-    fn refresh_frame<ERR>(
+    pub fn refresh_frame<ERR>(
         &mut self,
         signals: impl Iterator<Item=Result<(usize, isize), ERR>>,
     ) -> Result<(), ERR> {
@@ -234,76 +329,99 @@ impl CRT {
 
             let y = cycle / self.size.width;
             let x = cycle % self.size.width;
-            let sprite = register_x - 1..=register_x + 1;
-            let pixel = cycle + y; // pixel adjusted for display line feeds
-            let pattern = if sprite.contains(&(x as isize)) { b'#' } else { b'.' };
+            let sprite = register_x.saturating_sub(1)..=register_x.saturating_add(1);
 
-            self.pixels[pixel] = pattern;
+            self.frame.set(x, y, sprite.contains(&(x as isize)));
         }
 
         Ok(())
     }
 }
 
-impl ToString for CRT {
-    fn to_string(&self) -> String {
-        String::from_utf8_lossy(&self.pixels).to_string()
+impl Display for CRT {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", self.frame())
     }
 }
 
 /// represents a size value of height and width
 #[derive(Copy, Clone, Debug, Default)]
-struct Size {
-    height: usize,
-    width: usize,
+pub struct Size {
+    /// height
+    pub height: usize,
+    /// width
+    pub width: usize,
 }
 
 impl Size {
-    const fn new(width: usize, height: usize) -> Self {
+    /// a size of a width and height
+    #[must_use]
+    pub const fn new(width: usize, height: usize) -> Self {
         Self { height, width }
     }
 }
 
 /// total of signal processor of a `cpu`'s `cycle` and `register_x` output
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
+    where R: Read
+{
+    sum_signal_strengths(input, Mode::Strict).map(|(answer, _)| answer)
+}
+
+/// decode a `crt` frame from a `cpu`'s `cycle` and `register_x` output
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
+    where R: Read
+{
+    decode_frame(input, Mode::Strict).map(|(answer, _)| answer)
+}
+
+/// total signal strength, along with the number of invalid instructions skipped
+fn sum_signal_strengths<R>(input: R, mode: Mode) -> Result<(Answer, usize), AocError>
     where R: Read
 {
     const TRIGGER_FREQUENCY: usize = 40;
     const TRIGGER_OFFSET: usize = 20;
 
     let mut cpu = CPU::new();
-    let instructions = Instructions::new(input);
+    let instructions = Instructions::new(input, mode);
     let cycles = cpu.run_instructions(instructions);
-    let signals = SignalProcessor::new(cycles, TRIGGER_OFFSET, TRIGGER_FREQUENCY);
+    let mut signals = SignalProcessor::new(cycles, TRIGGER_OFFSET, TRIGGER_FREQUENCY);
+    let strengths = iter::from_fn(|| Some(signals.next()?.map(|strength| (Line(signals.line()), strength))));
+    let total = sum_everything(DAY, strengths)?;
 
-    Ok(sum_everything(signals).map(Box::new)?)
+    Ok((Answer::from(total), signals.skipped()))
 }
 
-/// decode a `crt` frame from a `cpu`'s `cycle` and `register_x` output
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// decoded crt frame, along with the number of invalid instructions skipped
+fn decode_frame<R>(input: R, mode: Mode) -> Result<(Answer, usize), AocError>
     where R: Read
 {
     const CRT_WIDTH: usize = 40;
     const CRT_HEIGHT: usize = 6;
 
     let mut cpu = CPU::new();
-    let instructions = Instructions::new(input);
-    let cycles = cpu.run_instructions(instructions);
+    let instructions = Instructions::new(input, mode);
+    let mut cycles = cpu.run_instructions(instructions);
     let mut crt = CRT::new(Size::new(CRT_WIDTH, CRT_HEIGHT));
 
-    crt.refresh_frame(cycles)?;
+    crt.refresh_frame(cycles.by_ref())?;
 
     // println!("{}", crt.to_string());
 
     // actual input interprets to "RKPJBPLA"
-    Ok(Box::new(crt.to_string().replace('\n', "")))
+    Ok((Answer::from(crt.frame()), cycles.skipped()))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
-
-    const INPUT: &str = "addx 15
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "addx 15
 addx -11
 addx 6
 addx -3
@@ -450,26 +568,127 @@ noop
 noop
 noop";
 
+/// day of the advent calendar
+const DAY: u8 = 10;
+
+/// title of the day's puzzle
+const TITLE: &str = "Cathode-Ray Tube";
+
+/// day 10 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn solve_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(Answer, usize), AocError> {
+        sum_signal_strengths(input, mode)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(), AocError> {
+        Instructions::new(input, mode).try_for_each(|instruction| instruction.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "13140" }]
+    }
+}
+
+/// day 10 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn solve_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(Answer, usize), AocError> {
+        decode_frame(input, mode)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(), AocError> {
+        Instructions::new(input, mode).try_for_each(|instruction| instruction.map(drop))
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######....." }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::solution::{assert_examples, Mode, Solution};
+
+    use super::{EXAMPLE_INPUT, Instruction, Instructions, PuzzleOne, PuzzleTwo};
+
     #[test]
-    #[allow(clippy::too_many_lines)]
-    fn puzzle_one() {
-        let expected = "13140";
+    fn locate_invalid_instructions() {
+        let mut instructions = Instructions::new(&b"noop\n\naddx x1"[..], Mode::Strict);
+
+        assert_eq!(instructions.next().map(Result::ok), Some(Some(Instruction::Noop)));
 
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+        let Some(Err(AocError::Parse { line, column, .. })) = instructions.next() else {
+            panic!("expected a parse error");
+        };
 
-        assert_eq!(actual, expected);
+        assert_eq!((line, column), (3, 6));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "##..##..##..##..##..##..##..##..##..##..###...###...###...###...###...###...###.####....####....####....####....####....#####.....#####.....#####.....#####.....######......######......######......###########.......#######.......#######.....";
+    fn skip_invalid_instructions_only_when_lenient() {
+        let input = EXAMPLE_INPUT.replacen("addx -11", "addx eleven", 1);
+        let skipped = EXAMPLE_INPUT.replacen("addx -11\n", "", 1);
+
+        let Err(AocError::Parse { line, .. }) = PuzzleOne.solve_in(&mut input.as_bytes(), Mode::Strict) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(line, 2);
 
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+        let lenient = PuzzleOne.solve_in(&mut input.as_bytes(), Mode::Lenient).expect("skipped invalid instruction");
+        let expected = PuzzleOne.solve(&mut skipped.as_bytes()).expect(crate::EXPECTED_PUZZLE_SOLUTION);
 
-        assert_eq!(actual, expected);
+        assert_eq!(lenient, (expected, 1));
+    }
+
+    #[test]
+    fn fail_when_the_register_or_signal_strength_overflows() {
+        let register = format!("noop\naddx {}", isize::MAX);
+        let signal = format!("addx {}\n{}", isize::MAX - 1, "noop\n".repeat(18));
+
+        for (input, expected) in [(register, "day 10, line 2"), (signal, "day 10, line 19")] {
+            match PuzzleOne.solve(&mut input.as_bytes()) {
+                Err(AocError::Overflow(record)) => assert_eq!(record, expected),
+                other => panic!("expected an overflow, got {other:?}"),
+            }
+        }
+
+        let sprite = format!("addx {}\naddx {}\n{}", isize::MAX - 1, isize::MIN, "noop\n".repeat(240));
+
+        assert!(PuzzleTwo.solve(&mut sprite.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
+    }
+
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
     }
 }
@@ -2,23 +2,26 @@
 
 extern crate nom;
 
-use std::{fmt, io};
+use std::fmt;
 use std::borrow::BorrowMut;
 use std::collections::VecDeque;
 use std::fmt::{Debug, Display, Formatter};
 use std::io::Read;
 use std::marker::PhantomData;
-use std::ops::{Add, Mul};
 use std::str::FromStr;
 
 use nom::branch::alt;
-use nom::bytes::complete::tag;
-use nom::character::complete::{alpha1, digit1, space1};
-use nom::IResult;
+use nom::character::complete::{alpha1, digit1, one_of, space1};
+use nom::combinator::rest;
 use nom::multi::separated_list1;
-use num_traits::Num;
+use nom::sequence::{delimited, terminated, tuple};
+use num_traits::{CheckedAdd, CheckedMul, Num};
 
-use crate::utils::{CleansedLines, io_error};
+use crate::answer::Answer;
+use crate::error::{AocError, ParseError};
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::Records;
+use crate::utils::parse::{expect, keyword, keyword_value, literal, parse_line, Parsed, unsigned};
 
 // for the second part of the puzzle I made the notes parser generic for the number type
 // used for calculating worry values, so that I can use 64 bit floating point values.
@@ -28,170 +31,133 @@ use crate::utils::{CleansedLines, io_error};
 /// iterator of notes of monkeys
 /// generic type `N` was necessary to make iterator of
 /// generic `Monkey<N>` types
-struct Notes<R, N> {
-    lines: CleansedLines<R>,
+pub struct Notes<R, N> {
+    records: Records<R>,
     phantom: PhantomData<N>,
 }
 
 impl<R, N> Notes<R, N>
     where R: Read
 {
-    fn new(input: R) -> Self {
+    /// iterate the notes of monkeys of an input
+    pub fn new(input: R) -> Self {
         Self {
-            lines: CleansedLines::new(input),
-            phantom: PhantomData::default(),
+            records: Records::new(input),
+            phantom: PhantomData,
         }
     }
 }
 
+/// lines of the notes of a monkey
+const NOTE_LINES: usize = 6;
+
 impl<R, N> Iterator for Notes<R, N>
     where R: Read,
           N: Num + FromStr + Display,
           <N as FromStr>::Err: Debug + Display
 {
-    type Item = io::Result<Monkey<N>>;
+    type Item = Result<Monkey<N>, AocError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // parses Monkey notes lazily
-        let (_, monkey_id) = match parse_monkey_id(&self.lines.next()?.ok()?) {
-            Ok(parsed) => parsed,
-            Err(err) => return Some(Err(io_error(&format!("invalid money identifier: {err}"))))
-        };
-
-        let (_, start_items) = match parse_start_items(&self.lines.next()?.ok()?) {
-            Ok(parsed) => parsed,
-            Err(err) => return Some(Err(io_error(&format!("invalid start items: {err}"))))
-        };
-
-        let (_, operation) = match parse_operation(&self.lines.next()?.ok()?) {
-            Ok(parsed) => parsed,
-            Err(err) => return Some(Err(io_error(&format!("invalid operation: {err}"))))
-        };
-
-        let (_, test) = match parse_test(&self.lines.next()?.ok()?) {
-            Ok(parsed) => parsed,
-            Err(err) => return Some(Err(io_error(&format!("invalid test: {err}"))))
+        // the notes of each monkey are a record, starting with its identifier
+        let (start, notes) = match self.records.next()? {
+            Ok(record) => record,
+            Err(err) => return Some(Err(err.into())),
         };
 
-        let (_, true_throw) = match parse_decision(&self.lines.next()?.ok()?, "true") {
-            Ok(parsed) => parsed,
-            Err(err) => return Some(Err(io_error(&format!("invalid operation: {err}"))))
-        };
-
-        let (_, false_throw) = match parse_decision(&self.lines.next()?.ok()?, "false") {
-            Ok(parsed) => parsed,
-            Err(err) => return Some(Err(io_error(&format!("invalid operation: {err}"))))
-        };
-
-        return Some(Ok(Monkey {
-            id: monkey_id,
-            items: start_items,
-            worried: operation,
-            test,
-            throws: [true_throw, false_throw],
-        }));
-
-        // parses the decision for a monkey; from notes
-        fn parse_decision<'a>(input: &'a str, result: &'a str) -> IResult<&'a str, usize> {
-            let (next, _) = space1(input)?;
-            let (next, _) = tag("If")(next)?;
-            let (next, _) = space1(next)?;
-            let (next, _) = tag(result)(next)?;
-            let (next, _) = tag(": throw to monkey")(next)?;
-            let (next, _) = space1(next)?;
-            let (next, monkey) = digit1(next)?;
-
-            Ok((next, monkey.parse().expect("")))
-        }
-
-        // parses the monkey identifier; from notes
-        fn parse_monkey_id(input: &str) -> IResult<&str, usize> {
-            let (next, _) = tag("Monkey")(input)?;
-            let (next, _) = space1(next)?;
-            let (next, id) = parse_number(next)?;
-            let (next, _) = tag(":")(next)?;
-
-            Ok((next, id))
-        }
+        return Some(parse_monkey(start, &notes));
 
-        // parses the monkey's operation; from notes
-        fn parse_operation<N>(input: &str) -> IResult<&str, Operation<N>>
+        // parses the notes of a monkey, a line each for its identifier, items, operation, test and decisions
+        fn parse_monkey<N>(start: usize, notes: &[String]) -> Result<Monkey<N>, AocError>
             where N: Num + FromStr + Display,
                   <N as FromStr>::Err: Debug + Display
         {
-            let (next, _) = space1(input)?;
-            let (next, _) = tag("Operation: new =")(next)?;
-            let (operation, _) = space1(next)?;
-
-            Ok(("", Operation::from_str(operation).expect("")))
+            let [id, items, operation, test, if_true, if_false] = notes else {
+                return Err(unexpected_size(start, notes));
+            };
+
+            let monkey_id = note(id, start, terminated(keyword("Monkey", unsigned), literal(":")))?;
+            let start_items = note(items, start + 1, keyword_value("Starting items", separated_list1(literal(", "), unsigned)))?;
+
+            let expression = note(operation, start + 2, keyword_value("Operation", keyword("new", keyword("=", rest))))?;
+            let worried = expression.parse::<Operation<N>>()
+                .map_err(|err| err.within(operation, expression).in_line(DAY, start + 2, operation))?;
+
+            let divisor = note(test, start + 3, keyword_value("Test", keyword("divisible", keyword("by", digit1))))?;
+            // a monkey divides by its test, it can't be 0
+            let test = match divisor.parse::<N>() {
+                Ok(by) if !by.is_zero() => by,
+                Ok(_) => return Err(ParseError::at(test, divisor, "expected a divisor other than 0").in_line(DAY, start + 3, test)),
+                Err(err) => return Err(ParseError::at(test, divisor, format!("'{divisor}' is not valid; {err}")).in_line(DAY, start + 3, test)),
+            };
+            let true_throw = note(if_true, start + 4, keyword_value("If true", throw))?;
+            let false_throw = note(if_false, start + 5, keyword_value("If false", throw))?;
+
+            Ok(Monkey {
+                id: monkey_id,
+                items: VecDeque::from(start_items),
+                worried,
+                test,
+                throws: [true_throw, false_throw],
+            })
         }
 
-        // parses starting items a monkey has; from notes
-        fn parse_start_items<N>(input: &str) -> IResult<&str, VecDeque<N>>
-            where N: Num + FromStr + Display,
-                  <N as FromStr>::Err: Debug + Display
-        {
-            let (next, _) = space1(input)?;
-            let (next, _) = tag("Starting items:")(next)?;
-            let (next, _) = space1(next)?;
-            let (next, list) = separated_list1(tag(", "), parse_number)(next)?;
-
-            Ok((next, VecDeque::from(list)))
-        }
+        // the notes of a monkey are too long, at the first line too many, or incomplete, at the end of the last line
+        fn unexpected_size(start: usize, notes: &[String]) -> AocError {
+            if let Some(extra) = notes.get(NOTE_LINES) {
+                return ParseError::new(1, format!("unexpected note, the notes of a monkey are {NOTE_LINES} lines"))
+                    .in_line(DAY, start + NOTE_LINES, extra);
+            }
 
-        // parses the monkey's observed test; from notes
-        fn parse_test<N>(input: &str) -> IResult<&str, N>
-            where N: Num + FromStr + Display,
-                  <N as FromStr>::Err: Debug + Display
-        {
-            let (next, _) = space1(input)?;
-            let (next, _) = tag("Test: divisible by")(next)?;
-            let (next, _) = space1(next)?;
-            let (next, test) = digit1(next)?;
+            let last = notes.last().map_or("", String::as_str);
 
-            Ok((next, test.parse().expect("")))
+            ParseError::new(last.len() + 1, format!("incomplete notes of a monkey, {} of {NOTE_LINES} lines", notes.len()))
+                .in_line(DAY, start + notes.len().saturating_sub(1), last)
         }
 
-        // parses a whole number as `N`
-        fn parse_number<N>(input: &str) -> IResult<&str, N>
-            where N: Num + FromStr + Display,
-                  <N as FromStr>::Err: Debug + Display
-        {
-            let (next, value) = digit1(input)?;
+        // the parsed value of a line of notes, or where parsing it failed
+        fn note<'a, T>(line: &'a str, number: usize, parser: impl FnMut(&'a str) -> Parsed<'a, T>) -> Result<T, AocError> {
+            parse_line(line, parser).map_err(|err| err.in_line(DAY, number, line))
+        }
 
-            Ok((next, value.parse().expect("")))
+        // the monkey thrown to on a decision, i.e. `throw to monkey 2`
+        fn throw(input: &str) -> Parsed<'_, usize> {
+            keyword("throw", keyword("to", keyword("monkey", unsigned)))(input)
         }
     }
 }
 
 /// Notes of a particular monkey
 #[derive(Debug)]
-#[allow(dead_code)]
-struct Monkey<N>
+pub struct Monkey<N>
     where N: Display
 {
     /// the identifier of the monkey observed
-    id: usize,
+    pub id: usize,
     /// items the monkey starts with
-    items: VecDeque<N>,
+    pub items: VecDeque<N>,
     /// the worry operation of the monkey
-    worried: Operation<N>,
+    pub worried: Operation<N>,
     /// the test the monkey uses to decide where to throw the current item
-    test: N,
+    pub test: N,
     /// the two monkeys that are thrown to, based on the worry test the monkey determines
-    throws: [usize; 2],
+    pub throws: [usize; 2],
 }
 
 /// the worry operations a monkey calculates
-enum Operation<N> {
+pub enum Operation<N> {
     ///  adds two values
     Add {
+        /// left hand side of the addition
         lhs: Operand<N>,
+        /// right hand side of the addition
         rhs: Operand<N>,
     },
-    // multiplies two values
+    /// multiplies two values
     Multiply {
+        /// left hand side of the multiplication
         lhs: Operand<N>,
+        /// right hand side of the multiplication
         rhs: Operand<N>,
     },
 }
@@ -208,13 +174,13 @@ impl<N> Debug for Operation<N>
 }
 
 impl<N> Operation<N>
-    where N: Copy + Add<Output=N> + Mul<Output=N>
+    where N: Copy + CheckedAdd + CheckedMul
 {
-    /// calculation the monkey performs before testing your worry level
-    fn calc(&self, old: N) -> N {
+    /// calculation the monkey performs before testing your worry level, none if it overflows
+    pub fn calc(&self, old: N) -> Option<N> {
         match self {
-            Self::Add { lhs, rhs } => lhs.value(old) + rhs.value(old),
-            Self::Multiply { lhs, rhs } => lhs.value(old) * rhs.value(old),
+            Self::Add { lhs, rhs } => lhs.value(old).checked_add(&rhs.value(old)),
+            Self::Multiply { lhs, rhs } => lhs.value(old).checked_mul(&rhs.value(old)),
         }
     }
 }
@@ -223,41 +189,28 @@ impl<N> FromStr for Operation<N>
     where N: Num + FromStr + Display,
           <N as FromStr>::Err: Display
 {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let (next, lhs) = parse_operand(input).map_err(|err| format!("'{input}' is not a valid operand; {err}"))?;
-        let (next, operator) = parse_operator(next).map_err(|err| format!("'{input}' is not a valid operator; {err}"))?;
-        let (_, rhs) = parse_operand(next).map_err(|err| format!("'{input}' is not a valid operand; {err}"))?;
+        let operand = || expect("expected an operand, `old` or a number", alt((alpha1, digit1)));
+        let operator = expect("expected an operator, `+` or `*`", one_of("+*"));
+
+        let (lhs, operator, rhs) = parse_line(input, tuple((operand(), delimited(space1, operator, space1), operand())))?;
 
-        let lhs = lhs.parse().map_err(|err| format!("'{lhs}' is not a valid operand; {err}"))?;
-        let rhs = rhs.parse().map_err(|err| format!("'{rhs}' is not a valid operand; {err}"))?;
+        let lhs = lhs.parse::<Operand<N>>().map_err(|err| err.within(input, lhs))?;
+        let rhs = rhs.parse::<Operand<N>>().map_err(|err| err.within(input, rhs))?;
 
-        return Ok(if operator == "+" {
+        Ok(if operator == '+' {
             Self::Add { lhs, rhs }
         } else {
             Self::Multiply { lhs, rhs }
-        });
-
-        // parses the operand `old` or a number
-        fn parse_operand(input: &str) -> IResult<&str, &str> {
-            alt((alpha1, digit1))(input)
-        }
-
-        // parses the operator a `+` or a `*`
-        fn parse_operator(input: &str) -> IResult<&str, &str> {
-            let (next, _) = space1(input)?;
-            let (next, operator) = alt((tag("*"), tag("+")))(next)?;
-            let (next, _) = space1(next)?;
-
-            Ok((next, operator))
-        }
+        })
     }
 }
 
 /// operand of monkeys worry operation
 #[derive(Copy, Clone)]
-enum Operand<N> {
+pub enum Operand<N> {
     /// the old value
     Old,
     /// a number of type `N`
@@ -279,7 +232,7 @@ impl<N> Operand<N>
     where N: Copy
 {
     /// gets the value of the operand
-    const fn value(&self, old: N) -> N {
+    pub const fn value(&self, old: N) -> N {
         match self {
             Self::Old => old,
             Self::Num(value) => *value
@@ -291,34 +244,53 @@ impl<N> FromStr for Operand<N>
     where N: FromStr + Display,
           <N as FromStr>::Err: Display
 {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(input: &str) -> Result<Self, Self::Err> {
         if input.trim().eq_ignore_ascii_case("old") {
             Ok(Self::Old)
         } else {
-            Ok(Self::Num(input.parse().map_err(|err| format!("'{input}' is an invalid operand; {err}"))?))
+            Ok(Self::Num(input.parse().map_err(|err| ParseError::new(1, format!("'{input}' is an invalid operand; {err}")))?))
         }
     }
 }
 
-const EXPECT_A_MONKEY: &str = "expected a note about a monkey";
+/// the monkeys of the notes, every monkey throws to monkeys in the notes
+fn read_monkeys<R>(input: R) -> Result<Vec<Monkey<usize>>, AocError>
+    where R: Read
+{
+    let monkeys = Notes::new(input).collect::<Result<Vec<Monkey<usize>>, _>>()?;
+
+    for monkey in &monkeys {
+        if let Some(missing) = monkey.throws.iter().find(|&&throw| throw >= monkeys.len()) {
+            return Err(AocError::logic(format!(
+                "monkey {} throws to monkey {missing}, which isn't in the notes of {} monkeys", monkey.id, monkeys.len()
+            )));
+        }
+    }
+
+    Ok(monkeys)
+}
 
-pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// level of monkey business of the two most active monkeys after 20 rounds, with relief
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
-    let mut monkeys = Notes::new(input).collect::<Result<Vec<Monkey<usize>>, _>>()
-        .map_err(|err| io_error(&format!("invalid notes of monkeys; {err}")))?;
+    let mut monkeys = read_monkeys(input)?;
     let mut inspected = (0..monkeys.len()).map(|_| 0_usize).collect::<Vec<_>>();
 
     for _round in 0..20 {
         for monkey_idx in 0..monkeys.len() {
             let mut queue = Vec::new();
-            let monkey: &mut _ = monkeys.get_mut(monkey_idx).expect(EXPECT_A_MONKEY);
+            let monkey = &mut monkeys[monkey_idx];
             let items = monkey.items.borrow_mut();
 
             for item in items {
-                let worry = monkey.worried.calc(*item) / 3;
+                let worry = monkey.worried.calc(*item).ok_or_else(|| worry_overflows(monkey.id, *item))? / 3;
 
                 let next_monkey = if worry % monkey.test == 0 {
                     monkey.throws[0]
@@ -328,15 +300,13 @@ pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
 
                 queue.push((next_monkey, worry));
 
-                *inspected.get_mut(monkey_idx).expect(EXPECT_A_MONKEY) += 1;
+                inspected[monkey_idx] += 1;
             }
 
             monkey.items.clear();
 
             for (next_monkey, item) in queue {
-                let monkey = monkeys.get_mut(next_monkey).expect(EXPECT_A_MONKEY);
-
-                monkey.items.push_back(item);
+                monkeys[next_monkey].items.push_back(item);
             }
         }
     }
@@ -344,30 +314,34 @@ pub fn puzzle_one<R>(input: R) -> io::Result<Box<dyn ToString>>
     inspected.sort_unstable();
     inspected.reverse();
 
-    let monkey_business: usize = inspected.iter().take(2).product();
-
-    Ok(Box::new(monkey_business))
+    monkey_business(&inspected)
 }
 
-pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
+/// level of monkey business of the two most active monkeys after 10,000 rounds, without relief
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
     where R: Read
 {
     // todo: figure out why f64 does not produce the expected answer
-    let mut monkeys = Notes::new(input).collect::<Result<Vec<Monkey<usize>>, _>>()
-        .map_err(|err| io_error(&format!("invalid notes of monkeys; {err}")))?;
+    let mut monkeys = read_monkeys(input)?;
     let mut inspected = (0..monkeys.len()).map(|_| 0_usize).collect::<Vec<_>>();
 
-    for _round in 1..=10000 {
-        // I came close but this is not my answer 😞, https://erri120.github.io/posts/2022-12-11/
-        let product = monkeys.iter().fold(1, |acc, nxt| acc * nxt.test);
+    // I came close but this is not my answer 😞, https://erri120.github.io/posts/2022-12-11/
+    let product = monkeys.iter()
+        .try_fold(1_usize, |acc, nxt| acc.checked_mul(nxt.test))
+        .ok_or_else(|| AocError::logic("the product of the divisors of the monkeys overflows"))?;
 
+    for _round in 1..=10000 {
         for monkey_idx in 0..monkeys.len() {
             let mut queue = Vec::new();
-            let monkey = monkeys.get_mut(monkey_idx).expect(EXPECT_A_MONKEY);
+            let monkey = &mut monkeys[monkey_idx];
             let items = monkey.items.borrow_mut();
 
             for item in items {
-                let worry = monkey.worried.calc(*item) % product;
+                let worry = monkey.worried.calc(*item).ok_or_else(|| worry_overflows(monkey.id, *item))? % product;
 
                 let next_monkey = if worry % monkey.test == 0 {
                     monkey.throws[0]
@@ -377,15 +351,13 @@ pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
 
                 queue.push((next_monkey, worry));
 
-                *inspected.get_mut(monkey_idx).expect(EXPECT_A_MONKEY) += 1;
+                inspected[monkey_idx] += 1;
             }
 
             monkey.items.clear();
 
             for (next_monkey, item) in queue {
-                let monkey = monkeys.get_mut(next_monkey).expect(EXPECT_A_MONKEY);
-
-                monkey.items.push_back(item);
+                monkeys[next_monkey].items.push_back(item);
             }
         }
     }
@@ -393,16 +365,25 @@ pub fn puzzle_two<R>(input: R) -> io::Result<Box<dyn ToString>>
     inspected.sort_unstable();
     inspected.reverse();
 
-    let monkey_business: usize = inspected.iter().take(2).product();
+    monkey_business(&inspected)
+}
 
-    Ok(Box::new(monkey_business))
+/// level of monkey business, the product of the two most inspections
+fn monkey_business(inspected: &[usize]) -> Result<Answer, AocError> {
+    inspected.iter()
+        .take(2)
+        .try_fold(1_usize, |acc, nxt| acc.checked_mul(*nxt))
+        .map(Answer::from)
+        .ok_or_else(|| AocError::logic("the level of monkey business overflows"))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::EXPECTED_PUZZLE_SOLUTION;
+/// an error of a worry level overflowing when a monkey inspects an item
+fn worry_overflows(monkey: usize, item: usize) -> AocError {
+    AocError::logic(format!("monkey {monkey} overflows the worry level of an item of {item} inspecting it"))
+}
 
-    const INPUT: &str = "Monkey 0:
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "Monkey 0:
   Starting items: 79, 98
   Operation: new = old * 19
   Test: divisible by 23
@@ -430,25 +411,133 @@ Monkey 3:
     If true: throw to monkey 0
     If false: throw to monkey 1";
 
+/// day of the advent calendar
+const DAY: u8 = 11;
+
+/// title of the day's puzzle
+const TITLE: &str = "Monkey in the Middle";
+
+/// day 11 puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        read_monkeys(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "10605" }]
+    }
+}
+
+/// day 11 puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        read_monkeys(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "2713310158" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+    use crate::solution::{assert_examples, Solution};
+
+    use super::{EXAMPLE_INPUT, Notes};
+
     #[test]
-    fn puzzle_one() {
-        let expected = "10605";
+    fn locate_invalid_notes() {
+        let input = EXAMPLE_INPUT.replace("Test: divisible by 23", "Test: divisible by x");
+
+        let Err(AocError::Parse { line, column, .. }) = Notes::<_, u64>::new(input.as_bytes()).collect::<Result<Vec<_>, _>>() else {
+            panic!("expected a parse error");
+        };
 
-        let actual = super::puzzle_one(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+        assert_eq!((line, column), (4, 22));
 
-        assert_eq!(actual, expected);
+        let input = EXAMPLE_INPUT.replacen("    If false: throw to monkey 3\n", "", 1);
+
+        let Err(AocError::Parse { line, message, .. }) = Notes::<_, u64>::new(input.as_bytes()).collect::<Result<Vec<_>, _>>() else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, message.as_str()), (5, "incomplete notes of a monkey, 5 of 6 lines"));
+
+        let input = EXAMPLE_INPUT.replacen("\n\nMonkey 1:", "\nMonkey 1:", 1);
+
+        let Err(AocError::Parse { line, snippet, .. }) = Notes::<_, u64>::new(input.as_bytes()).collect::<Result<Vec<_>, _>>() else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, snippet.as_str()), (7, "Monkey 1:"));
+
+        let input = EXAMPLE_INPUT.replacen("Test: divisible by 19", "Test: divisible by 0", 1);
+
+        let Err(AocError::Parse { line, column, .. }) = Notes::<_, u64>::new(input.as_bytes()).collect::<Result<Vec<_>, _>>() else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (11, 22));
     }
 
     #[test]
-    fn puzzle_two() {
-        let expected = "2713310158";
+    fn reject_throws_to_missing_monkeys() {
+        let input = EXAMPLE_INPUT.replacen("throw to monkey 3", "throw to monkey 9", 1);
 
-        let actual = super::puzzle_two(INPUT.as_bytes())
-            .expect(EXPECTED_PUZZLE_SOLUTION)
-            .to_string();
+        for solution in [&super::PuzzleOne as &dyn Solution, &super::PuzzleTwo] {
+            let Err(AocError::Logic(message)) = solution.solve(&mut input.as_bytes()) else {
+                panic!("expected a logic error");
+            };
 
-        assert_eq!(actual, expected);
+            assert!(message.starts_with("monkey 0 throws to monkey 9"), "unexpected message; {message}");
+        }
+    }
+
+    #[test]
+    fn fail_when_a_worry_level_overflows() {
+        let input = EXAMPLE_INPUT.replacen("Starting items: 79, 98", &format!("Starting items: {}", usize::MAX), 1);
+
+        for solution in [&super::PuzzleOne as &dyn Solution, &super::PuzzleTwo] {
+            let Err(AocError::Logic(message)) = solution.solve(&mut input.as_bytes()) else {
+                panic!("expected a logic error");
+            };
+
+            assert!(message.starts_with("monkey 0 overflows the worry level"), "unexpected message; {message}");
+        }
+    }
+
+    #[test]
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
+    }
+
+    #[test]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
     }
 }
@@ -0,0 +1,170 @@
+//! Errors of solving puzzles; reading input, parsing it or making sense of it
+
+use std::{fmt, io};
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// An error solving a puzzle
+#[derive(Debug)]
+pub enum AocError {
+    /// the input couldn't be read
+    Io(io::Error),
+    /// a line of input isn't valid
+    Parse {
+        /// day of the puzzle
+        day: u8,
+        /// line number of the input, starting at 1
+        line: usize,
+        /// column of the line, starting at 1
+        column: usize,
+        /// the line of input
+        snippet: String,
+        /// what is wrong with it
+        message: String,
+    },
+    /// the input is valid, but doesn't make sense for the puzzle
+    Logic(String),
+    /// a total overflowed its integer type, at a record of input
+    Overflow(String),
+}
+
+impl AocError {
+    /// a parse error of a line of a day's input
+    #[must_use]
+    pub fn parse(day: u8, line: usize, snippet: &str, error: ParseError) -> Self {
+        Self::Parse {
+            day,
+            line,
+            column: error.column,
+            snippet: snippet.to_string(),
+            message: error.message,
+        }
+    }
+
+    /// a logic error
+    pub fn logic(message: impl Into<String>) -> Self {
+        Self::Logic(message.into())
+    }
+
+    /// an overflow of a total at a record of input, i.e. `elf 3, line 12`
+    pub fn overflow(record: impl Display) -> Self {
+        Self::Overflow(record.to_string())
+    }
+
+    /// renders the error like a compiler diagnostic, pointing out the offending column of a parse error
+    #[must_use]
+    pub fn render(&self) -> String {
+        match self {
+            Self::Parse { day, line, column, snippet, message } => {
+                let gutter = " ".repeat(line.to_string().len());
+                let marker = " ".repeat(column.saturating_sub(1));
+
+                format!(
+                    "error: {message}\n{gutter}--> day {day}, line {line}, column {column}\n\
+                     {gutter} |\n{line} | {snippet}\n{gutter} | {marker}^"
+                )
+            }
+            _ => format!("error: {self}"),
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(fmt, "{err}"),
+            Self::Parse { day, line, column, message, .. } =>
+                write!(fmt, "day {day}, line {line}, column {column}: {message}"),
+            Self::Logic(message) => write!(fmt, "{message}"),
+            Self::Overflow(record) => write!(fmt, "total overflows at {record}"),
+        }
+    }
+}
+
+impl Error for AocError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// A parse error at a column of a line of input, without the line's context
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    /// column of the line, starting at 1
+    pub column: usize,
+    /// what is wrong with the line
+    pub message: String,
+}
+
+impl ParseError {
+    /// an error at a column of a line
+    pub fn new(column: usize, message: impl Into<String>) -> Self {
+        Self { column, message: message.into() }
+    }
+
+    /// an error at the start of `part`, a slice of the `line`; at the start of the line if it isn't
+    pub fn at(line: &str, part: &str, message: impl Into<String>) -> Self {
+        let offset = (part.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+        let inside = line.len().checked_sub(part.len()).is_some_and(|last| offset <= last);
+
+        Self::new(if inside { offset + 1 } else { 1 }, message)
+    }
+
+    /// an error of `part`, a slice of the `line`, as an error of the `line`
+    #[must_use]
+    pub fn within(self, line: &str, part: &str) -> Self {
+        let offset = Self::at(line, part, "").column - 1;
+
+        Self::new(self.column + offset, self.message)
+    }
+
+    /// the error in the context of a line of a day's input
+    #[must_use]
+    pub fn in_line(self, day: u8, line: usize, snippet: &str) -> AocError {
+        AocError::parse(day, line, snippet, self)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{AocError, ParseError};
+
+    #[test]
+    fn locate_parse_errors() {
+        let line = "move 1 from 2 to x";
+
+        assert_eq!(ParseError::at(line, &line[17..], "no stack").column, 18);
+        assert_eq!(ParseError::at(line, "elsewhere", "no stack").column, 1);
+
+        assert_eq!(ParseError::new(2, "no stack").within(line, &line[14..]).column, 16);
+    }
+
+    #[test]
+    fn render_diagnostics() {
+        let error = ParseError::new(3, "'x' is not a valid direction").in_line(9, 12, "R x");
+
+        assert_eq!(error.to_string(), "day 9, line 12, column 3: 'x' is not a valid direction");
+        assert_eq!(
+            error.render(),
+            "error: 'x' is not a valid direction\n  --> day 9, line 12, column 3\n   |\n12 | R x\n   |   ^"
+        );
+        assert_eq!(AocError::logic("no marker found").render(), "error: no marker found");
+    }
+}
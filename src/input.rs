@@ -0,0 +1,123 @@
+//! Puzzle input sources; the puzzle input folder, a file or stdin
+
+use std::{env, fs, io};
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::solution::Solution;
+
+/// environment variable overriding the folder of puzzle input files
+pub const INPUT_DIR_VARIABLE: &str = "AOC_INPUT_DIR";
+/// default folder of puzzle input files
+pub const PUZZLE_INPUT_ROOT: &str = "puzzle_input";
+
+/// Where puzzle input is read from
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum InputSource {
+    /// each puzzle's input file in the puzzle input folder
+    #[default]
+    PuzzleFolder,
+    /// one input file for every puzzle
+    File(PathBuf),
+    /// standard input, for every puzzle
+    Stdin,
+}
+
+/// parse an input source from a command line argument, `-` is stdin
+impl FromStr for InputSource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source.trim() {
+            "" => Err(String::from("an input path is required")),
+            "-" => Ok(Self::Stdin),
+            path => Ok(Self::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Resolves and reads the input of puzzles
+#[derive(Debug)]
+pub enum Inputs {
+    /// input files in a folder, named by each puzzle
+    Folder(PathBuf),
+    /// one input file for every puzzle
+    File(PathBuf),
+    /// standard input read up front, for every puzzle
+    Stdin(Vec<u8>),
+}
+
+impl Inputs {
+    /// resolve inputs of a source, the puzzle input folder can be
+    /// overridden by the `AOC_INPUT_DIR` environment variable
+    ///
+    /// # Errors
+    ///
+    /// fails if stdin can't be read
+    pub fn new(source: InputSource) -> io::Result<Self> {
+        Ok(match source {
+            InputSource::PuzzleFolder => Self::Folder(
+                env::var_os(INPUT_DIR_VARIABLE).map_or_else(|| PathBuf::from(PUZZLE_INPUT_ROOT), PathBuf::from)
+            ),
+            InputSource::File(path) => Self::File(path),
+            InputSource::Stdin => {
+                let mut input = Vec::new();
+
+                io::stdin().lock().read_to_end(&mut input)?;
+
+                Self::Stdin(input)
+            }
+        })
+    }
+
+    /// describes where a puzzle's input is read from
+    pub fn path(&self, solution: &dyn Solution) -> String {
+        match self {
+            Self::Folder(folder) => folder.join(solution.input_file()).display().to_string(),
+            Self::File(path) => path.display().to_string(),
+            Self::Stdin(_) => String::from("<stdin>"),
+        }
+    }
+
+    /// reads a puzzle's input
+    ///
+    /// # Errors
+    ///
+    /// fails if the puzzle's input file can't be read
+    pub fn read(&self, solution: &dyn Solution) -> io::Result<Vec<u8>> {
+        match self {
+            Self::Folder(folder) => fs::read(folder.join(solution.input_file())),
+            Self::File(path) => fs::read(path),
+            Self::Stdin(input) => Ok(input.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::dec_06;
+
+    use super::{Inputs, InputSource};
+
+    #[test]
+    fn parse_input_sources() {
+        assert_eq!("-".parse::<InputSource>(), Ok(InputSource::Stdin));
+        assert_eq!("inputs/day-6.txt".parse::<InputSource>(), Ok(InputSource::File(PathBuf::from("inputs/day-6.txt"))));
+        assert!("".parse::<InputSource>().is_err());
+    }
+
+    #[test]
+    fn resolve_input_paths() {
+        let folder = Inputs::Folder(PathBuf::from("elsewhere"));
+        let file = Inputs::File(PathBuf::from("stress.txt"));
+        let stdin = Inputs::Stdin(b"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_vec());
+
+        assert_eq!(folder.path(&dec_06::PuzzleOne), PathBuf::from("elsewhere").join("2022-12-06.txt").display().to_string());
+        assert_eq!(file.path(&dec_06::PuzzleOne), "stress.txt");
+        assert_eq!(stdin.path(&dec_06::PuzzleOne), "<stdin>");
+        assert_eq!(stdin.read(&dec_06::PuzzleOne).expect("stdin input"), b"mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+    }
+}
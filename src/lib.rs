@@ -0,0 +1,47 @@
+#![deny(clippy::all)]
+#![deny(clippy::pedantic)]
+#![deny(clippy::nursery)]
+#![deny(clippy::cargo)]
+#![deny(missing_docs)]
+
+#![allow(clippy::items_after_statements)] // code organization is ok
+#![allow(clippy::upper_case_acronyms)]
+
+//! AOC 2022 Oxidized 🦀
+//!
+//! Each day's puzzle module exposes its input parsers, puzzle models and solvers,
+//! i.e. the day 10 [`CPU`](dec_10::CPU) or the day 7 [`FileSystem`](dec_07::FileSystem),
+//! along with the [`Solution`](solution::Solution)s the puzzle runner registers in
+//! [`SOLUTIONS`](solution::SOLUTIONS).
+
+pub mod answer;
+pub mod bench;
+pub mod cli;
+pub mod dec_01;
+pub mod dec_02;
+pub mod dec_02_one;
+pub mod dec_02_two;
+pub mod dec_03;
+pub mod dec_03_one;
+pub mod dec_03_two;
+pub mod dec_04;
+pub mod dec_05;
+pub mod dec_06;
+pub mod dec_07;
+pub mod dec_08;
+pub mod dec_09;
+pub mod dec_10;
+pub mod dec_11;
+pub mod error;
+pub mod input;
+pub mod pool;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod selection;
+pub mod solution;
+pub mod utils;
+pub mod verify;
+
+#[cfg(test)]
+const EXPECTED_PUZZLE_SOLUTION: &str = "expected puzzle to provide a solution";
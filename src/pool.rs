@@ -0,0 +1,123 @@
+//! A scoped thread pool that hands back results in order
+
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// maps every item on `jobs` threads, emitting results in item order as soon as
+/// every result before them is available; stops at the first emit error
+///
+/// # Errors
+///
+/// the first error emitting a result
+pub fn ordered_map<I, T, E, W, M>(items: &[I], jobs: NonZeroUsize, work: W, mut emit: M) -> Result<(), E>
+    where I: Sync,
+          T: Send,
+          W: Fn(&I) -> T + Sync,
+          M: FnMut(T) -> Result<(), E>,
+{
+    let next_item = AtomicUsize::new(0);
+    let stopped = AtomicBool::new(false);
+    let jobs = jobs.get().min(items.len()).max(1);
+
+    thread::scope(|scope| {
+        let (sender, results) = mpsc::channel();
+
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let (next_item, stopped, work) = (&next_item, &stopped, &work);
+
+            scope.spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    let idx = next_item.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(idx) else { break };
+
+                    if sender.send((idx, work(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        // only workers hold senders, so results end when all workers are done
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next_result = 0;
+
+        for (idx, result) in results {
+            pending.insert(idx, result);
+
+            while let Some(result) = pending.remove(&next_result) {
+                next_result += 1;
+
+                if let Err(err) = emit(result) {
+                    stopped.store(true, Ordering::Relaxed);
+
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::ordered_map;
+
+    #[test]
+    fn emit_results_in_order() {
+        let items = (0..32_u64).collect::<Vec<_>>();
+        let jobs = NonZeroUsize::new(4).expect("non zero jobs");
+        let mut emitted = Vec::new();
+
+        ordered_map(
+            &items,
+            jobs,
+            |item| {
+                // finish later items first
+                thread::sleep(Duration::from_micros(32 - item));
+
+                item * 2
+            },
+            |result| {
+                emitted.push(result);
+
+                Ok::<(), ()>(())
+            },
+        ).expect("all results emitted");
+
+        assert_eq!(emitted, items.iter().map(|item| item * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stop_at_first_emit_error() {
+        let items = (0..8).collect::<Vec<_>>();
+        let jobs = NonZeroUsize::new(2).expect("non zero jobs");
+        let mut emitted = Vec::new();
+
+        let result = ordered_map(
+            &items,
+            jobs,
+            |item| *item,
+            |item| if item == 3 {
+                Err(item)
+            } else {
+                emitted.push(item);
+
+                Ok(())
+            },
+        );
+
+        assert_eq!(result, Err(3));
+        assert_eq!(emitted, vec![0, 1, 2]);
+    }
+}
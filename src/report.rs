@@ -0,0 +1,236 @@
+//! Reports of puzzle results in text, json or csv formats
+
+use std::io;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::answer::Answer;
+
+/// Formats results can be reported in
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Format {
+    /// `label: answer` lines
+    #[default]
+    Text,
+    /// an array of json objects
+    Json,
+    /// comma separated values with a header
+    Csv,
+}
+
+impl Format {
+    /// file extension of the format
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(match source.trim().to_lowercase().as_str() {
+            "text" | "txt" => Self::Text,
+            "json" => Self::Json,
+            "csv" => Self::Csv,
+            _ => return Err(format!("'{source}' is not a valid format, expected text, json or csv"))
+        })
+    }
+}
+
+/// The result of running one puzzle
+#[derive(Clone, Debug)]
+pub struct Record {
+    /// day of the puzzle
+    pub day: u8,
+    /// part of the puzzle
+    pub part: u8,
+    /// label of the puzzle
+    pub label: String,
+    /// path of the input file the puzzle was solved with
+    pub input_file: String,
+    /// time it took to solve the puzzle
+    pub duration: Duration,
+    /// answer of the puzzle, or why it failed
+    pub outcome: Result<Answer, String>,
+}
+
+/// A record of a json report, its answer serialized as typed by [`Answer`]
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    day: u8,
+    part: u8,
+    label: &'a str,
+    answer: Option<&'a Answer>,
+    input_file: &'a str,
+    duration_us: u128,
+    error: Option<&'a str>,
+}
+
+/// Writes records of puzzle results in a format
+pub struct Report<W>
+    where W: Write
+{
+    format: Format,
+    output: W,
+    records: usize,
+}
+
+impl<W> Report<W>
+    where W: Write
+{
+    /// start a report, writing any header the format requires
+    ///
+    /// # Errors
+    ///
+    /// fails if the header can't be written
+    pub fn new(format: Format, mut output: W) -> io::Result<Self> {
+        match format {
+            Format::Text => {}
+            Format::Json => output.write_all(b"[")?,
+            Format::Csv => output.write_all(b"day,part,label,answer,input_file,duration_us,error\n")?,
+        }
+
+        Ok(Self { format, output, records: 0 })
+    }
+
+    /// write a record of a puzzle result
+    ///
+    /// # Errors
+    ///
+    /// fails if the record can't be written
+    pub fn write(&mut self, record: &Record) -> io::Result<()> {
+        let (answer, error) = match &record.outcome {
+            Ok(answer) => (Some(answer), None),
+            Err(error) => (None, Some(error.as_str())),
+        };
+
+        match self.format {
+            Format::Text => match &record.outcome {
+                Ok(answer) => writeln!(self.output, "{}: {answer}", record.label)?,
+                Err(error) => writeln!(self.output, "{}: error; {error}", record.label)?,
+            },
+            Format::Json => {
+                let separator = if self.records == 0 { "" } else { "," };
+
+                write!(self.output, "{separator}\n  ")?;
+
+                serde_json::to_writer(&mut self.output, &JsonRecord {
+                    day: record.day,
+                    part: record.part,
+                    label: &record.label,
+                    answer,
+                    input_file: &record.input_file,
+                    duration_us: record.duration.as_micros(),
+                    error,
+                })?;
+            }
+            Format::Csv => writeln!(
+                self.output,
+                "{},{},{},{},{},{},{}",
+                record.day,
+                record.part,
+                csv_field(&record.label),
+                csv_field(&answer.map(ToString::to_string).unwrap_or_default()),
+                csv_field(&record.input_file),
+                record.duration.as_micros(),
+                csv_field(error.unwrap_or_default()),
+            )?,
+        }
+
+        self.records += 1;
+
+        Ok(())
+    }
+
+    /// complete the report, writing any footer the format requires
+    ///
+    /// # Errors
+    ///
+    /// fails if the footer can't be written
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.format == Format::Json {
+            self.output.write_all(if self.records == 0 { b"]\n" } else { b"\n]\n" })?;
+        }
+
+        self.output.flush()?;
+
+        Ok(self.output)
+    }
+}
+
+/// a csv field, quoted if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::answer::Answer;
+
+    use super::{Format, Record, Report};
+
+    fn report(format: Format, outcomes: &[Result<&str, &str>]) -> String {
+        let mut report = Report::new(format, Vec::new()).expect("a report");
+
+        for (part, outcome) in (1..).zip(outcomes) {
+            report.write(&Record {
+                day: 5,
+                part,
+                label: format!("2022-12-05 puzzle {part}"),
+                input_file: String::from("puzzle_input/2022-12-05.txt"),
+                duration: Duration::from_micros(42),
+                outcome: outcome.map(Answer::from).map_err(ToString::to_string),
+            }).expect("a written record");
+        }
+
+        String::from_utf8(report.finish().expect("a finished report")).expect("a utf8 report")
+    }
+
+    #[test]
+    fn report_text() {
+        let actual = report(Format::Text, &[Ok("CMZ"), Err("no crates")]);
+
+        assert_eq!(actual, "2022-12-05 puzzle 1: CMZ\n2022-12-05 puzzle 2: error; no crates\n");
+    }
+
+    #[test]
+    fn report_json() {
+        let actual = report(Format::Json, &[Ok("a:\"b\""), Err("bad\nline")]);
+
+        let expected = "[
+  {\"day\":5,\"part\":1,\"label\":\"2022-12-05 puzzle 1\",\"answer\":{\"text\":\"a:\\\"b\\\"\"},\"input_file\":\"puzzle_input/2022-12-05.txt\",\"duration_us\":42,\"error\":null},
+  {\"day\":5,\"part\":2,\"label\":\"2022-12-05 puzzle 2\",\"answer\":null,\"input_file\":\"puzzle_input/2022-12-05.txt\",\"duration_us\":42,\"error\":\"bad\\nline\"}
+]
+";
+
+        assert_eq!(actual, expected);
+        assert_eq!(report(Format::Json, &[]), "[]\n");
+    }
+
+    #[test]
+    fn report_csv() {
+        let actual = report(Format::Csv, &[Ok("1,2"), Err("say \"what\"")]);
+
+        let expected = "day,part,label,answer,input_file,duration_us,error
+5,1,2022-12-05 puzzle 1,\"1,2\",puzzle_input/2022-12-05.txt,42,
+5,2,2022-12-05 puzzle 2,,puzzle_input/2022-12-05.txt,42,\"say \"\"what\"\"\"
+";
+
+        assert_eq!(actual, expected);
+    }
+}
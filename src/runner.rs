@@ -0,0 +1,489 @@
+//! The puzzle runner; runs, verifies or scaffolds puzzles as the command line asks
+
+use std::{env, fs};
+use std::fs::{File, remove_file};
+use std::io;
+use std::io::{BufWriter, IsTerminal};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::bench::Summary;
+use crate::cli::{Command, Options};
+use crate::dec_01;
+use crate::dec_01::{calorie_statistics, ElfTotal, top_elves, total_calories};
+use crate::dec_02::rps::best_decoding;
+use crate::dec_02_one;
+use crate::error::AocError;
+use crate::input::Inputs;
+use crate::pool::ordered_map;
+use crate::report::{Record, Report};
+use crate::scaffold::new_day;
+use crate::solution::{Mode, Solution, SOLUTIONS};
+use crate::utils::io_error;
+use crate::verify::{compare, Difference, EXPECTED_RESULTS, parse_expected, pending};
+
+/// runs the command of the command line options, returning the number of puzzles that failed
+///
+/// # Errors
+///
+/// fails if the command line, puzzle input or expected results are invalid, or results can't be written
+pub fn run() -> io::Result<usize> {
+    let options = Options::parse(env::args().skip(1)).map_err(|err| io_error(&err))?;
+
+    let inputs = Inputs::new(options.input.clone())?;
+
+    match &options.command {
+        Command::Run => run_puzzles(&options, &inputs),
+        Command::Examples => run_examples(&options),
+        Command::Verify => verify_puzzles(&options, &inputs),
+        Command::New { day, title } => {
+            for changed in new_day(Path::new("."), *day, title)? {
+                println!("{}", changed.display());
+            }
+
+            Ok(0)
+        }
+        Command::Top { k } => list_top_elves(&inputs, k.get()),
+        Command::Stats => report_calorie_statistics(&inputs),
+        Command::Decode => report_best_decoding(&inputs),
+    }
+}
+
+/// runs all selected puzzles, returning the number of puzzles that failed
+fn run_puzzles(options: &Options, inputs: &Inputs) -> io::Result<usize> {
+    let output = get_buffered_writer(format!("aoc-2022-rs-results.{}", options.format.extension()))?;
+    let mut report = Report::new(options.format, output)?;
+    let mut failures = Vec::new();
+    let mut passed = 0;
+
+    let selected = selected_solutions(options);
+
+    // results are emitted in registry order, regardless of which puzzle finishes first
+    ordered_map(
+        &selected,
+        options.jobs,
+        |solution| run_puzzle(*solution, options, inputs),
+        |(record, console)| {
+            match &record.outcome {
+                Ok(_) => {
+                    passed += 1;
+
+                    print!("{console}");
+                }
+                Err(err) => {
+                    eprintln!("{} failed:\n{console}", record.label);
+
+                    failures.push((record.label.clone(), err.clone()));
+                }
+            }
+
+            report.write(&record)
+        },
+    )?;
+
+    report.finish()?;
+
+    println!("\n{passed} passed, {} failed", failures.len());
+
+    for (label, err) in &failures {
+        println!("  {label}: {err}");
+    }
+
+    Ok(failures.len())
+}
+
+/// solves the worked examples of all selected puzzles, returning the number of examples that failed
+fn run_examples(options: &Options) -> io::Result<usize> {
+    let mut failed = 0;
+    let mut passed = 0;
+
+    ordered_map(
+        &selected_solutions(options),
+        options.jobs,
+        |solution| {
+            let solved = solution.examples().iter()
+                .map(|example| (example.expected, example.solve(*solution)))
+                .collect::<Vec<_>>();
+
+            (*solution, solved)
+        },
+        |(solution, solved)| {
+            for (idx, (expected, actual)) in solved.into_iter().enumerate() {
+                let example = format!("{} ({}) example {}", solution.label(), solution.title(), idx + 1);
+
+                match actual {
+                    Ok(actual) if actual == expected => {
+                        passed += 1;
+
+                        println!("{example}: expected {expected}, actual {actual}");
+                    }
+                    Ok(actual) => {
+                        failed += 1;
+
+                        println!("{example}: expected {expected}, actual {actual} MISMATCH");
+                    }
+                    Err(err) => {
+                        failed += 1;
+
+                        println!("{example}: expected {expected}, failed; {err}");
+                    }
+                }
+            }
+
+            Ok::<(), io::Error>(())
+        },
+    )?;
+
+    println!("\n{passed} examples passed, {failed} failed");
+
+    Ok(failed)
+}
+
+/// solves all selected puzzles and compares their answers with the expected results,
+/// returning the number of differences
+fn verify_puzzles(options: &Options, inputs: &Inputs) -> io::Result<usize> {
+    let expected = fs::read_to_string(EXPECTED_RESULTS)
+        .map_err(|err| io_error(&format!("couldn't read '{EXPECTED_RESULTS}'; {err}")))?;
+    let expected = parse_expected(&expected).map_err(|err| io_error(&err))?;
+
+    // only expect answers of selected puzzles, or of puzzles that are not solved at all
+    let expected = expected.into_iter()
+        .filter(|(label, _)| SOLUTIONS.iter()
+            .find(|solution| solution.label() == *label)
+            .is_none_or(|solution| options.is_selected(*solution))
+        )
+        .collect::<Vec<_>>();
+
+    let mut actual = Vec::new();
+
+    ordered_map(
+        &selected_solutions(options),
+        options.jobs,
+        |solution| solve_puzzle(*solution, inputs, options.mode).0,
+        |record| {
+            actual.push((record.label, record.outcome.map_or_else(|err| format!("error; {err}"), |answer| answer.to_string())));
+
+            Ok::<(), io::Error>(())
+        },
+    )?;
+
+    let differences = compare(&expected, &actual);
+    let colored = env::var_os("NO_COLOR").is_none() && io::stdout().is_terminal();
+
+    let pending = pending(&expected);
+
+    for difference in &differences {
+        println!("{}", difference.render(colored));
+    }
+
+    for label in &pending {
+        println!("  {label}: pending, not verified");
+    }
+
+    println!(
+        "\n{} of {} expected answers verified, {} pending, {} differences",
+        expected.len() - pending.len() - differences.iter().filter(|diff| !matches!(diff, Difference::Extra { .. })).count(),
+        expected.len(),
+        pending.len(),
+        differences.len()
+    );
+
+    Ok(differences.len())
+}
+
+/// solutions selected by the command line options, in registry order
+fn selected_solutions(options: &Options) -> Vec<&'static dyn Solution> {
+    SOLUTIONS.iter()
+        .copied()
+        .filter(|solution| options.is_selected(*solution))
+        .collect()
+}
+
+/// solves one puzzle, recording its answer or why it failed along with what to show on the console;
+/// the answer, or a diagnostic of why it failed
+fn run_puzzle(solution: &dyn Solution, options: &Options, inputs: &Inputs) -> (Record, String) {
+    let (mut record, solved) = solve_puzzle(solution, inputs, options.mode);
+
+    let Solved { input, read, parse, skipped } = match solved {
+        Ok(solved) => solved,
+        Err(diagnostic) => return (record, diagnostic),
+    };
+
+    let Ok(answer) = &record.outcome else { return (record, String::new()) };
+
+    let skipped = if skipped > 0 { format!(" [skipped {skipped} invalid lines]") } else { String::new() };
+
+    let mut console = if options.time {
+        format!(
+            "{} ({}): {answer}{skipped} [read {read:?}, parse {parse:?}, parse and solve {:?}]\n",
+            record.label, solution.title(), record.duration
+        )
+    } else {
+        format!("{} ({}): {answer}{skipped}\n", record.label, solution.title())
+    };
+
+    if let Some(runs) = options.bench {
+        let timings = (0..runs.get())
+            .map(|_| {
+                let started = Instant::now();
+
+                solution.solve_in(&mut input.as_slice(), options.mode).map(|_| started.elapsed())
+            })
+            .collect::<Result<Vec<_>, AocError>>();
+
+        match timings.map(Summary::new) {
+            Ok(Some(summary)) => console = format!("{console}  bench: {summary}\n"),
+            Ok(None) => {}
+            Err(err) => {
+                record.outcome = Err(format!("benchmark failed; {err}"));
+                console = err.render();
+            }
+        }
+    }
+
+    (record, console)
+}
+
+/// A puzzle that was solved
+struct Solved {
+    /// input of the puzzle
+    input: Vec<u8>,
+    /// how long it took to read the input
+    read: Duration,
+    /// how long it took to parse the input, on its own
+    parse: Duration,
+    /// number of invalid lines of input skipped
+    skipped: usize,
+}
+
+/// lists the `k` elves of day 1 carrying the most calories, returning 1 if the input isn't valid
+fn list_top_elves(inputs: &Inputs, k: usize) -> io::Result<usize> {
+    let input = read_calories(inputs)?;
+
+    // totals of stress inputs may not fit `usize`
+    match top_elves::<u128, _>(input.as_slice(), k).and_then(|top| Ok((total_calories(&top)?, top))) {
+        Ok((total, top)) => {
+            for ElfTotal { elf, calories } in &top {
+                println!("elf {elf}: {calories}");
+            }
+
+            println!("\ntotal of the top {} elves: {total}", top.len());
+
+            Ok(0)
+        }
+        Err(err) => {
+            eprintln!("{}", err.render());
+
+            Ok(1)
+        }
+    }
+}
+
+/// reports statistics of the calories the elves of day 1 carry, returning 1 if the input isn't valid
+fn report_calorie_statistics(inputs: &Inputs) -> io::Result<usize> {
+    let input = read_calories(inputs)?;
+
+    match calorie_statistics(input.as_slice()) {
+        Ok(statistics) => {
+            println!("{statistics}");
+
+            Ok(0)
+        }
+        Err(err) => {
+            eprintln!("{}", err.render());
+
+            Ok(1)
+        }
+    }
+}
+
+/// reads the input of day 1
+fn read_calories(inputs: &Inputs) -> io::Result<Vec<u8>> {
+    inputs.read(&dec_01::PuzzleOne)
+        .map_err(|err| io_error(&format!("couldn't read '{}'; {err}", inputs.path(&dec_01::PuzzleOne))))
+}
+
+/// reports the score of every decoding of the day 2 strategy guide, returning 1 if the input isn't valid
+fn report_best_decoding(inputs: &Inputs) -> io::Result<usize> {
+    let input = inputs.read(&dec_02_one::PuzzleOne)
+        .map_err(|err| io_error(&format!("couldn't read '{}'; {err}", inputs.path(&dec_02_one::PuzzleOne))))?;
+
+    match best_decoding(input.as_slice()) {
+        Ok(decodings) => {
+            println!("{decodings}");
+
+            Ok(0)
+        }
+        Err(err) => {
+            eprintln!("{}", err.render());
+
+            Ok(1)
+        }
+    }
+}
+
+/// reads and solves one puzzle in a mode, recording its answer or why it failed,
+/// along with how it was solved, or a diagnostic of why it failed
+fn solve_puzzle(solution: &dyn Solution, inputs: &Inputs, mode: Mode) -> (Record, Result<Solved, String>) {
+    let mut record = Record {
+        day: solution.day(),
+        part: solution.part(),
+        label: solution.label(),
+        input_file: inputs.path(solution),
+        duration: Duration::ZERO,
+        outcome: Err(String::from("not solved")),
+    };
+
+    // read the input up front so solve timings exclude file io
+    let started = Instant::now();
+
+    let input = match inputs.read(solution) {
+        Ok(input) => input,
+        Err(err) => {
+            let err = format!("couldn't read '{}'; {err}", record.input_file);
+
+            record.outcome = Err(err.clone());
+
+            return (record, Err(format!("error: {err}")));
+        }
+    };
+
+    let read = started.elapsed();
+
+    // time parsing on its own, puzzles parse their input again while solving
+    let started = Instant::now();
+
+    if let Err(err) = solution.parse_in(&mut input.as_slice(), mode) {
+        record.outcome = Err(err.to_string());
+
+        return (record, Err(err.render()));
+    }
+
+    let parse = started.elapsed();
+
+    let started = Instant::now();
+    let solved = solution.solve_in(&mut input.as_slice(), mode);
+
+    record.duration = started.elapsed();
+
+    match solved {
+        Ok((answer, skipped)) => {
+            record.outcome = Ok(answer);
+
+            (record, Ok(Solved { input, read, parse, skipped }))
+        }
+        Err(err) => {
+            record.outcome = Err(err.to_string());
+
+            (record, Err(err.render()))
+        }
+    }
+}
+
+fn get_buffered_writer<P: AsRef<Path>>(output_path: P) -> io::Result<BufWriter<File>> {
+    let output_path = output_path.as_ref();
+
+    if output_path.exists() {
+        remove_file(output_path)?;
+    }
+
+    let output_file = File::create(output_path)?;
+
+    Ok(BufWriter::new(output_file))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+    use std::num::NonZeroUsize;
+
+    use crate::cli::Options;
+    use crate::input::{Inputs, InputSource, PUZZLE_INPUT_ROOT};
+    use crate::pool::ordered_map;
+    use super::{run_puzzle, solve_puzzle};
+    use crate::solution::{Mode, SOLUTIONS};
+    use crate::verify::{compare, parse_expected};
+
+    #[test]
+    fn keep_running_every_puzzle_when_a_day_fails() {
+        let folder = env::temp_dir().join(format!("aoc-2022-bad-day-{}", process::id()));
+
+        fs::create_dir_all(&folder).expect("input folder");
+
+        for solution in SOLUTIONS {
+            fs::copy(format!("{PUZZLE_INPUT_ROOT}/{}", solution.input_file()), folder.join(solution.input_file()))
+                .expect("puzzle input copied");
+        }
+
+        // a folder whose size overflows fails both puzzles of day 7
+        fs::write(folder.join("2022-12-07.txt"), format!("$ cd /\n$ ls\n{} a\n1 b\n", usize::MAX)).expect("bad input");
+
+        let options = Options { jobs: NonZeroUsize::new(4).expect("non zero jobs"), ..Options::default() };
+        let inputs = Inputs::Folder(folder.clone());
+        let mut records = Vec::new();
+
+        ordered_map(SOLUTIONS, options.jobs, |solution| run_puzzle(*solution, &options, &inputs), |(record, _)| {
+            records.push(record);
+
+            Ok::<(), ()>(())
+        }).expect("every puzzle reported");
+
+        fs::remove_dir_all(&folder).expect("input folder removed");
+
+        assert_eq!(records.len(), SOLUTIONS.len());
+
+        // every other day reports as it does on its own, whether it is solved or not
+        let alone = Inputs::new(InputSource::PuzzleFolder).expect("puzzle input folder");
+
+        for (solution, record) in SOLUTIONS.iter().zip(records) {
+            if record.day == 7 {
+                assert!(record.outcome.is_err(), "{} answered {:?}", record.label, record.outcome);
+            } else {
+                assert_eq!(record.outcome, solve_puzzle(*solution, &alone, Mode::Strict).0.outcome, "{}", record.label);
+            }
+        }
+    }
+
+    #[test]
+    fn expected_answers_cover_every_solution() {
+        const RESULT_SEPARATOR: char = ':';
+
+        let expected = include_str!("../aoc-2022-rs-expected-results.txt").trim();
+
+        let expected_labels = expected.lines()
+            .map(|line| line.split_once(RESULT_SEPARATOR).map_or(line, |(label, _)| label).trim())
+            .collect::<Vec<_>>();
+
+        let registered_labels = SOLUTIONS.iter()
+            .map(|solution| solution.label())
+            .collect::<Vec<_>>();
+
+        assert_eq!(registered_labels, expected_labels);
+    }
+
+    #[test]
+    fn verify_correct_answers_for_refactoring() {
+        let expected = parse_expected(include_str!("../aoc-2022-rs-expected-results.txt"))
+            .expect("expected results to be formatted expectedly");
+
+        let inputs = Inputs::new(InputSource::PuzzleFolder).expect("puzzle input folder");
+
+        // solve live, rather than checking whatever was last written to the results file
+        let actual = SOLUTIONS.iter()
+            .map(|solution| {
+                let (record, _) = solve_puzzle(*solution, &inputs, Mode::Strict);
+
+                (record.label, record.outcome.map_or_else(|err| format!("error; {err}"), |answer| answer.to_string()))
+            })
+            .collect::<Vec<_>>();
+
+        // days scaffolded but not solved yet are pending, which isn't compared
+        let differences = compare(&expected, &actual);
+
+        assert!(
+            differences.is_empty(),
+            "answers differ from expected results:\n{}",
+            differences.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+        );
+    }
+}
\ No newline at end of file
@@ -0,0 +1,340 @@
+//! Scaffolding of a new day's puzzle module, registration and input file
+
+use std::{fs, io};
+use std::path::{Path, PathBuf};
+
+use crate::input::PUZZLE_INPUT_ROOT;
+use crate::utils::io_error;
+use crate::verify::{EXPECTED_RESULTS, PENDING};
+
+/// module declarations of the puzzle library
+const LIB_MODULE: &str = "src/lib.rs";
+/// registry of puzzle solutions
+const SOLUTION_MODULE: &str = "src/solution.rs";
+
+/// template of a day's puzzle module
+const TEMPLATE: &str = r#"//! [AOC 2022 Day {DAY}](https://adventofcode.com/2022/day/{DAY})
+
+use std::io::Read;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::solution::{Example, Mode, Solution};
+use crate::utils::CleansedLines;
+
+/// parse the lines of the puzzle input
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn parse_input<R>(input: R) -> Result<Vec<String>, AocError>
+    where R: Read
+{
+    Ok(CleansedLines::new(input).collect::<Result<_, _>>()?)
+}
+
+/// solve puzzle one
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_one<R>(input: R) -> Result<Answer, AocError>
+    where R: Read
+{
+    let _lines = parse_input(input)?;
+
+    Err(AocError::logic("day {DAY} puzzle one is not solved yet"))
+}
+
+/// solve puzzle two
+///
+/// # Errors
+///
+/// fails if the puzzle input can't be read or isn't valid
+pub fn puzzle_two<R>(input: R) -> Result<Answer, AocError>
+    where R: Read
+{
+    let _lines = parse_input(input)?;
+
+    Err(AocError::logic("day {DAY} puzzle two is not solved yet"))
+}
+
+/// worked example from the puzzle text
+const EXAMPLE_INPUT: &str = "";
+
+/// day of the advent calendar
+const DAY: u8 = {DAY};
+
+/// title of the day's puzzle
+const TITLE: &str = {TITLE};
+
+/// day {DAY} puzzle one
+pub struct PuzzleOne;
+
+impl Solution for PuzzleOne {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 1 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_one(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "" }]
+    }
+}
+
+/// day {DAY} puzzle two
+pub struct PuzzleTwo;
+
+impl Solution for PuzzleTwo {
+    fn day(&self) -> u8 { DAY }
+
+    fn part(&self) -> u8 { 2 }
+
+    fn title(&self) -> &'static str { TITLE }
+
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError> {
+        puzzle_two(input)
+    }
+
+    fn parse_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(), AocError> {
+        parse_input(input).map(drop)
+    }
+
+    fn examples(&self) -> &'static [Example] {
+        &[Example { input: EXAMPLE_INPUT, expected: "" }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::solution::assert_examples;
+
+    #[test]
+    #[ignore = "day {DAY} is not solved yet"]
+    fn puzzle_one() {
+        assert_examples(&super::PuzzleOne);
+    }
+
+    #[test]
+    #[ignore = "day {DAY} is not solved yet"]
+    fn puzzle_two() {
+        assert_examples(&super::PuzzleTwo);
+    }
+}
+"#;
+
+/// generates a new day's puzzle module from a template, registers it, expects its puzzles to be pending
+/// and creates an empty puzzle input file, returning the files created or changed
+///
+/// nothing is written if the day already exists
+///
+/// # Errors
+///
+/// fails if `root` isn't the puzzle repository, the day already exists or files can't be written
+pub fn new_day(root: &Path, day: u8, title: &str) -> io::Result<Vec<PathBuf>> {
+    let module = format!("dec_{day:02}");
+    let module_path = root.join("src").join(format!("{module}.rs"));
+    let lib_path = root.join(LIB_MODULE);
+    let solution_path = root.join(SOLUTION_MODULE);
+    let expected_path = root.join(EXPECTED_RESULTS);
+    let input_path = root.join(PUZZLE_INPUT_ROOT).join(format!("2022-12-{day:02}.txt"));
+
+    if !lib_path.exists() || !solution_path.exists() || !expected_path.exists() {
+        return Err(io_error(&format!("'{}' is not the root of the puzzle repository", root.display())));
+    }
+
+    if module_path.exists() {
+        return Err(io_error(&format!("day {day} already exists; '{}'", module_path.display())));
+    }
+
+    // prepare every change before writing anything
+    let lib_source = declare_module(&fs::read_to_string(&lib_path)?, &module).map_err(|err| io_error(&err))?;
+    let solution_source = register_solutions(&fs::read_to_string(&solution_path)?, &module).map_err(|err| io_error(&err))?;
+    let expected_source = expect_pending(&fs::read_to_string(&expected_path)?, day).map_err(|err| io_error(&err))?;
+    // the title is written as a string literal, escaping any quotes or backslashes
+    let day_source = TEMPLATE.replace("{DAY}", &day.to_string()).replace("{TITLE}", &format!("{title:?}"));
+
+    fs::write(&module_path, day_source)?;
+    fs::write(&lib_path, lib_source)?;
+    fs::write(&solution_path, solution_source)?;
+    fs::write(&expected_path, expected_source)?;
+
+    let mut changed = vec![module_path, lib_path, solution_path, expected_path];
+
+    if !input_path.exists() {
+        if let Some(folder) = input_path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+
+        fs::write(&input_path, "")?;
+
+        changed.push(input_path);
+    }
+
+    Ok(changed)
+}
+
+/// declares a day's module among the other `pub mod dec_XX;` declarations
+fn declare_module(source: &str, module: &str) -> Result<String, String> {
+    let declaration = format!("pub mod {module};");
+    let mut lines = source.lines().collect::<Vec<_>>();
+
+    if lines.contains(&declaration.as_str()) {
+        return Err(format!("'{declaration}' is already declared"));
+    }
+
+    let days = lines.iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod dec_"))
+        .map(|(idx, line)| (idx, *line))
+        .collect::<Vec<_>>();
+
+    let (last, _) = *days.last().ok_or("no `pub mod dec_XX;` declarations found")?;
+
+    // declarations are sorted, so insert before the first declaration that sorts after it
+    let at = days.iter()
+        .find(|(_, line)| *line > declaration.as_str())
+        .map_or(last + 1, |(idx, _)| *idx);
+
+    lines.insert(at, &declaration);
+
+    Ok(with_line_ending(&lines.join("\n"), source))
+}
+
+/// imports a day's module into the solution registry and registers both of its puzzles
+fn register_solutions(source: &str, module: &str) -> Result<String, String> {
+    const IMPORT: &str = "use crate::{";
+    const REGISTRY: &str = "pub static SOLUTIONS: &[&dyn Solution] = &[";
+
+    let mut lines = source.lines().map(ToString::to_string).collect::<Vec<_>>();
+
+    // add module to the sorted module imports
+    let import = lines.iter_mut()
+        .find(|line| line.starts_with(IMPORT))
+        .ok_or("no registry module imports found")?;
+
+    let mut modules = import[IMPORT.len()..]
+        .trim_end_matches("};")
+        .split(',')
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .collect::<Vec<_>>();
+
+    modules.push(module);
+    modules.sort_unstable();
+
+    *import = format!("{IMPORT}{}}};", modules.join(", "));
+
+    // register puzzles before the first registered puzzle of a later day
+    let start = lines.iter()
+        .position(|line| line == REGISTRY)
+        .ok_or("no solution registry found")? + 1;
+
+    let end = start + lines[start..].iter()
+        .position(|line| line == "];")
+        .ok_or("solution registry is not terminated")?;
+
+    let entry = format!("    &{module}::");
+
+    let at = (start..end)
+        .find(|idx| lines[*idx].trim_start().trim_start_matches('&') > module)
+        .unwrap_or(end);
+
+    lines.insert(at, format!("{entry}PuzzleTwo,"));
+    lines.insert(at, format!("{entry}PuzzleOne,"));
+
+    Ok(with_line_ending(&lines.join("\n"), source))
+}
+
+/// expects a day's puzzles to be pending, among the expected results sorted by label
+fn expect_pending(source: &str, day: u8) -> Result<String, String> {
+    let mut lines = source.lines().map(ToString::to_string).collect::<Vec<_>>();
+
+    for part in ["one", "two"] {
+        let label = format!("2022-12-{day:02} puzzle {part}");
+
+        if lines.iter().any(|line| line.split_once(':').is_some_and(|(expected, _)| expected.trim() == label)) {
+            return Err(format!("'{label}' is already expected"));
+        }
+
+        // results are sorted, so insert before the first result that sorts after it
+        let at = lines.iter()
+            .position(|line| !line.trim().is_empty() && line.as_str() > label.as_str())
+            .unwrap_or_else(|| lines.iter().rposition(|line| !line.trim().is_empty()).map_or(0, |last| last + 1));
+
+        lines.insert(at, format!("{label}: {PENDING}"));
+    }
+
+    Ok(with_line_ending(&lines.join("\n"), source))
+}
+
+/// keeps the trailing line ending of the original source
+fn with_line_ending(source: &str, original: &str) -> String {
+    if original.ends_with('\n') {
+        format!("{source}\n")
+    } else {
+        source.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{declare_module, expect_pending, register_solutions};
+
+    #[test]
+    fn declare_modules_in_order() {
+        let source = "pub mod cli;\npub mod dec_01;\npub mod dec_11;\npub mod input;\n";
+
+        assert_eq!(declare_module(source, "dec_05"), Ok(String::from("pub mod cli;\npub mod dec_01;\npub mod dec_05;\npub mod dec_11;\npub mod input;\n")));
+        assert_eq!(declare_module(source, "dec_12"), Ok(String::from("pub mod cli;\npub mod dec_01;\npub mod dec_11;\npub mod dec_12;\npub mod input;\n")));
+        assert!(declare_module(source, "dec_11").is_err());
+    }
+
+    #[test]
+    fn register_solutions_in_order() {
+        let source = "use crate::{dec_01, dec_11};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &dec_01::PuzzleOne,
+    &dec_11::PuzzleTwo,
+];
+";
+
+        let expected = "use crate::{dec_01, dec_11, dec_12};
+
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &dec_01::PuzzleOne,
+    &dec_11::PuzzleTwo,
+    &dec_12::PuzzleOne,
+    &dec_12::PuzzleTwo,
+];
+";
+
+        assert_eq!(register_solutions(source, "dec_12"), Ok(String::from(expected)));
+
+        let registered = register_solutions(source, "dec_05").expect("registered solutions");
+
+        assert!(registered.contains("use crate::{dec_01, dec_05, dec_11};"));
+        assert!(registered.contains("    &dec_01::PuzzleOne,\n    &dec_05::PuzzleOne,\n    &dec_05::PuzzleTwo,\n    &dec_11::PuzzleTwo,"));
+    }
+
+    #[test]
+    fn expect_pending_puzzles_in_order() {
+        let source = "2022-12-01 puzzle one: 1\n2022-12-11 puzzle two: 2\n";
+
+        assert_eq!(
+            expect_pending(source, 5),
+            Ok(String::from("2022-12-01 puzzle one: 1\n2022-12-05 puzzle one: pending\n2022-12-05 puzzle two: pending\n2022-12-11 puzzle two: 2\n"))
+        );
+        assert!(expect_pending(&expect_pending(source, 12).expect("expected results"), 12).is_err());
+    }
+}
@@ -0,0 +1,125 @@
+//! Command line selection of puzzle days and parts
+
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// the days an advent calendar has puzzles for
+const CALENDAR_DAYS: RangeInclusive<u8> = 1..=25;
+/// the parts each day's puzzle has
+const PUZZLE_PARTS: RangeInclusive<u8> = 1..=2;
+
+/// A selection of puzzle days, optionally restricted to one part;
+/// i.e. `7`, `9:2`, `3..=6`, `3..7` or `3..=6:1`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Selection {
+    /// days selected
+    days: RangeInclusive<u8>,
+    /// part selected, all parts if `None`
+    part: Option<u8>,
+}
+
+impl Selection {
+    /// checks if a day's puzzle part is selected
+    #[must_use]
+    pub fn matches(&self, day: u8, part: u8) -> bool {
+        self.days.contains(&day) && self.part.is_none_or(|selected| selected == part)
+    }
+}
+
+/// parse a selection from a command line argument
+impl FromStr for Selection {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let source = source.trim();
+
+        let (days, part) = match source.split_once(':') {
+            Some((days, part)) => (days, Some(parse_part(part, source)?)),
+            None => (source, None)
+        };
+
+        let days = if let Some((start, end)) = days.split_once("..=") {
+            parse_day(start, source)?..=parse_day(end, source)?
+        } else if let Some((start, end)) = days.split_once("..") {
+            let start = parse_day(start, source)?;
+            let end = parse_day(end, source)?;
+
+            if end <= start {
+                return Err(format!("'{source}' is an empty range of days"));
+            }
+
+            start..=end - 1
+        } else {
+            let day = parse_day(days, source)?;
+
+            day..=day
+        };
+
+        if days.is_empty() {
+            return Err(format!("'{source}' is an empty range of days"));
+        }
+
+        return Ok(Self { days, part });
+
+        // parse a calendar day
+        fn parse_day(day: &str, source: &str) -> Result<u8, String> {
+            let day = day.trim().parse::<u8>()
+                .map_err(|err| format!("'{}' in '{source}' is not a valid day; {err}", day.trim()))?;
+
+            if CALENDAR_DAYS.contains(&day) {
+                Ok(day)
+            } else {
+                Err(format!(
+                    "day {day} in '{source}' is out of range, expected {} to {}",
+                    CALENDAR_DAYS.start(), CALENDAR_DAYS.end()
+                ))
+            }
+        }
+
+        // parse a puzzle part
+        fn parse_part(part: &str, source: &str) -> Result<u8, String> {
+            let part = part.trim().parse::<u8>()
+                .map_err(|err| format!("'{}' in '{source}' is not a valid part; {err}", part.trim()))?;
+
+            if PUZZLE_PARTS.contains(&part) {
+                Ok(part)
+            } else {
+                Err(format!(
+                    "part {part} in '{source}' is out of range, expected {} or {}",
+                    PUZZLE_PARTS.start(), PUZZLE_PARTS.end()
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selection;
+
+    #[test]
+    fn parse_selections() {
+        let day = "7".parse::<Selection>().expect("a day selection");
+
+        assert!(day.matches(7, 1) && day.matches(7, 2) && !day.matches(8, 1));
+
+        let part = "9:2".parse::<Selection>().expect("a day and part selection");
+
+        assert!(part.matches(9, 2) && !part.matches(9, 1));
+
+        let inclusive = "3..=6".parse::<Selection>().expect("an inclusive range selection");
+
+        assert!(inclusive.matches(3, 1) && inclusive.matches(6, 2) && !inclusive.matches(7, 1));
+
+        let exclusive = "3..6:1".parse::<Selection>().expect("an exclusive range selection");
+
+        assert!(exclusive.matches(5, 1) && !exclusive.matches(5, 2) && !exclusive.matches(6, 1));
+    }
+
+    #[test]
+    fn reject_invalid_selections() {
+        for invalid in ["", "0", "26", "seven", "9:3", "9:", "6..=3", "3..3", "1..=30"] {
+            assert!(invalid.parse::<Selection>().is_err(), "'{invalid}' should be rejected");
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Puzzle solutions and the registry of all solved puzzles
+
+use std::io::Read;
+
+use crate::answer::Answer;
+use crate::error::AocError;
+use crate::{dec_01, dec_02_one, dec_02_two, dec_03_one, dec_03_two, dec_04, dec_05, dec_06, dec_07, dec_08, dec_09, dec_10, dec_11};
+
+/// every puzzle solved, in the order they are run
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &dec_01::PuzzleOne,
+    &dec_01::PuzzleTwo,
+    &dec_02_one::PuzzleOne,
+    &dec_02_two::PuzzleTwo,
+    &dec_03_one::PuzzleOne,
+    &dec_03_two::PuzzleTwo,
+    &dec_04::PuzzleOne,
+    &dec_04::PuzzleTwo,
+    &dec_05::PuzzleOne,
+    &dec_05::PuzzleTwo,
+    &dec_06::PuzzleOne,
+    &dec_06::PuzzleTwo,
+    &dec_07::PuzzleOne,
+    &dec_07::PuzzleTwo,
+    &dec_08::PuzzleOne,
+    &dec_08::PuzzleTwo,
+    &dec_09::PuzzleOne,
+    &dec_09::PuzzleTwo,
+    &dec_10::PuzzleOne,
+    &dec_10::PuzzleTwo,
+    &dec_11::PuzzleOne,
+    &dec_11::PuzzleTwo,
+];
+
+/// How a puzzle treats lines of input that aren't valid
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// fail with the first line that isn't valid
+    #[default]
+    Strict,
+    /// skip lines that aren't valid, counting how many were skipped
+    Lenient,
+}
+
+/// A worked example from the puzzle text and its expected answer
+#[derive(Copy, Clone, Debug)]
+pub struct Example {
+    /// example input
+    pub input: &'static str,
+    /// expected answer of the example
+    pub expected: &'static str,
+}
+
+impl Example {
+    /// solves the example, returning its actual answer
+    ///
+    /// # Errors
+    ///
+    /// fails if the solution fails to solve the example
+    pub fn solve(&self, solution: &dyn Solution) -> Result<String, AocError> {
+        solution.solve(&mut self.input.as_bytes()).map(|answer| answer.to_string())
+    }
+}
+
+/// A solution to one part of a day's puzzle
+pub trait Solution: Sync {
+    /// day of the advent calendar the puzzle is from
+    fn day(&self) -> u8;
+
+    /// part of the day's puzzle this solves
+    fn part(&self) -> u8;
+
+    /// title of the day's puzzle
+    fn title(&self) -> &'static str;
+
+    /// solve the puzzle for an input
+    ///
+    /// # Errors
+    ///
+    /// fails if the input can't be read or isn't valid
+    fn solve(&self, input: &mut dyn Read) -> Result<Answer, AocError>;
+
+    /// solve the puzzle for an input in a mode, answering along with the number of invalid lines skipped;
+    /// puzzles that can't skip invalid lines solve strictly in either mode
+    ///
+    /// # Errors
+    ///
+    /// fails if the input can't be read or, when strict, isn't valid
+    fn solve_in(&self, input: &mut dyn Read, _mode: Mode) -> Result<(Answer, usize), AocError> {
+        self.solve(input).map(|answer| (answer, 0))
+    }
+
+    /// parse an input in a mode without solving the puzzle, so parsing can be timed on its own
+    ///
+    /// # Errors
+    ///
+    /// fails if the input can't be read or, when strict, isn't valid
+    fn parse_in(&self, input: &mut dyn Read, mode: Mode) -> Result<(), AocError>;
+
+    /// worked examples from the puzzle text
+    fn examples(&self) -> &'static [Example];
+
+    /// label of the puzzle, i.e. `2022-12-01 puzzle one`
+    fn label(&self) -> String {
+        let part = if self.part() == 1 { "one" } else { "two" };
+
+        format!("2022-12-{:02} puzzle {part}", self.day())
+    }
+
+    /// name of the puzzle's input file, i.e. `2022-12-01.txt`
+    fn input_file(&self) -> String {
+        format!("2022-12-{:02}.txt", self.day())
+    }
+}
+
+/// asserts a solution answers all of its examples as expected
+///
+/// # Panics
+///
+/// if the solution has no examples or fails to answer one as expected
+#[cfg(test)]
+pub fn assert_examples(solution: &dyn Solution) {
+    assert!(!solution.examples().is_empty(), "{} has no examples", solution.label());
+
+    for (idx, example) in solution.examples().iter().enumerate() {
+        let actual = example.solve(solution).expect(crate::EXPECTED_PUZZLE_SOLUTION);
+
+        assert_eq!(actual, example.expected, "{} example {} failed", solution.label(), idx + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::io::Read;
+
+    use crate::error::AocError;
+
+    use super::{Mode, Solution, SOLUTIONS};
+
+    /// reads its input, then fails
+    struct FailingReader<'a> {
+        input: &'a [u8],
+    }
+
+    impl Read for FailingReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.input.is_empty() {
+                Err(io::Error::other("the reader failed"))
+            } else {
+                self.input.read(buf)
+            }
+        }
+    }
+
+    /// asserts a solution fails to read an input, rather than answering what it could read
+    fn assert_io_error(solution: &dyn Solution, mut input: impl Read, cause: &str) {
+        match solution.solve(&mut input) {
+            Err(AocError::Io(err)) =>
+                assert!(err.to_string().contains(cause), "{} failed unexpectedly; {err}", solution.label()),
+            Err(err) =>
+                panic!("{} failed without an io error; {err}", solution.label()),
+            Ok(answer) =>
+                panic!("{} answered {answer} instead of failing", solution.label()),
+        }
+    }
+
+    #[test]
+    fn every_puzzle_fails_to_read_invalid_utf8() {
+        for solution in SOLUTIONS {
+            let mut input = format!("{}\n", solution.examples()[0].input).into_bytes();
+
+            input.extend_from_slice(b"\xff\xfe\n");
+
+            assert_io_error(*solution, input.as_slice(), "valid UTF-8");
+        }
+    }
+
+    #[test]
+    fn every_puzzle_fails_when_the_reader_fails() {
+        for solution in SOLUTIONS {
+            let input = format!("{}\n", solution.examples()[0].input);
+
+            assert_io_error(*solution, FailingReader { input: input.as_bytes() }, "the reader failed");
+        }
+    }
+
+    #[test]
+    fn every_puzzle_parses_its_examples_on_their_own() {
+        for solution in SOLUTIONS {
+            for example in solution.examples() {
+                let parsed = solution.parse_in(&mut example.input.as_bytes(), Mode::Strict);
+
+                assert!(parsed.is_ok(), "{} failed to parse an example; {parsed:?}", solution.label());
+            }
+
+            let mut input = format!("{}\n", solution.examples()[0].input).into_bytes();
+
+            input.extend_from_slice(b"\xff\xfe\n");
+
+            assert!(
+                matches!(solution.parse_in(&mut input.as_slice(), Mode::Strict), Err(AocError::Io(_))),
+                "{} parsed invalid utf8", solution.label()
+            );
+        }
+    }
+
+    #[test]
+    fn registry_is_ordered_by_day_and_part() {
+        let registered = SOLUTIONS.iter()
+            .map(|solution| (solution.day(), solution.part()))
+            .collect::<Vec<_>>();
+
+        let mut ordered = registered.clone();
+
+        ordered.sort_unstable();
+        ordered.dedup();
+
+        assert_eq!(registered, ordered);
+    }
+}
@@ -1,21 +1,53 @@
-use std::error::Error;
+//! Utilities shared by the puzzles
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
 use std::io;
-use std::io::{BufRead, BufReader, ErrorKind, Lines, Read};
-use std::ops::{Add, ControlFlow};
+use std::io::{BufRead, BufReader, Lines, Read};
+use std::iter;
+
+use num_traits::{CheckedAdd, Zero};
+
+use crate::error::AocError;
+
+pub use bitset::{BitSet, common_items, Mask};
+pub use grid::{Direction, Grid};
+pub use try_iter::TryIterator;
 
-/// Iterates a file line by line skipping empty lines and honoring io errors
+mod bitset;
+mod grid;
+pub mod parse;
+mod try_iter;
+
+/// Iterates a file line by line skipping empty lines and honoring io errors;
+/// iteration ends after the first io error
 pub struct CleansedLines<R> {
     lines: Lines<BufReader<R>>,
+    line: usize,
+    failed: bool,
 }
 
 impl<R> CleansedLines<R>
     where R: Read
 {
+    /// iterate the non empty lines of an input
     pub fn new(input: R) -> Self {
         Self {
             lines: BufReader::new(input).lines(),
+            line: 0,
+            failed: false,
         }
     }
+
+    /// line number of the last line read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// iterate the non empty lines along with their line numbers
+    pub fn numbered(mut self) -> impl Iterator<Item=io::Result<(usize, String)>> {
+        iter::from_fn(move || Some(self.next()?.map(|line| (self.line, line))))
+    }
 }
 
 impl<R> Iterator for CleansedLines<R>
@@ -24,85 +56,178 @@ impl<R> Iterator for CleansedLines<R>
     type Item = io::Result<String>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut line = self.lines.next()?.ok()?;
+        if self.failed {
+            return None;
+        }
 
         // read file line by line skipping empty lines, honoring io errors
-        while line.trim().is_empty() {
-            line = self.lines.next()?.ok()?;
-        }
+        loop {
+            self.line += 1;
+
+            match self.lines.next()? {
+                Ok(line) if line.trim().is_empty() => {}
+                Ok(line) => return Some(Ok(line)),
+                Err(err) => {
+                    // a failing reader may keep failing, don't read past it
+                    self.failed = true;
 
-        Some(Ok(line))
+                    return Some(Err(line_io_error(self.line, &err)));
+                }
+            }
+        }
     }
 }
 
-/// find the first item that is present in all input iterators
-pub fn find_matching<I, Iter, IntoIter, P, ER, E, const N: usize>(
-    input: &[IntoIter; N],
-    mut predicate: P,
-    build_error: ER,
-) -> Result<Iter::Item, E>
-    where I: Eq + Copy,
-          Iter: Iterator<Item=I>,
-          IntoIter: IntoIterator<Item=I, IntoIter=Iter> + Clone,
-          E: Error,
-          P: FnMut(I) -> Result<(), E>,
-          ER: Fn(&str) -> E,
+/// Iterates a file in records, groups of non empty lines separated by blank lines, honoring io errors;
+/// each record along with the line number of its first line, iteration ends after the first io error
+pub struct Records<R> {
+    lines: Lines<BufReader<R>>,
+    line: usize,
+    failed: bool,
+}
+
+impl<R> Records<R>
+    where R: Read
 {
-    if input.len() < 2 { return Err(build_error("Provide more than one set to find matching items")); }
-
-    let mut found_match = None;
-    let items = input[0].clone().into_iter();
-
-    for item in items {
-        predicate(item)?;
-
-        let matches = input
-            .iter().skip(1)
-            .filter_map(|items| {
-                items.clone().into_iter()
-                    .find_map(|itm| match predicate(itm) {
-                        // validate item failed
-                        Err(err) => Some(Err(err)),
-                        // validate item passed, item matches
-                        Ok(_) if itm == item => Some(Ok(itm)),
-                        // validate item passed, item does not match
-                        Ok(_) => None,
-                    })
-            })
-            .collect::<Result<Vec<_>, E>>()?;
-
-        if matches.len() == input.len() - 1 {
-            found_match = Some(item);
-            break;
+    /// iterate the records of an input
+    pub fn new(input: R) -> Self {
+        Self {
+            lines: BufReader::new(input).lines(),
+            line: 0,
+            failed: false,
         }
     }
 
-    found_match.ok_or_else(|| build_error("No matches found"))
+    /// line number of the last line read, starting at 1
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl<R> Iterator for Records<R>
+    where R: Read
+{
+    type Item = io::Result<(usize, Vec<String>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let mut start = 0;
+        let mut record = Vec::new();
+
+        // gather lines until a blank line ends the record, skipping blank lines before it
+        loop {
+            let Some(next) = self.lines.next() else {
+                return (!record.is_empty()).then_some(Ok((start, record)));
+            };
+
+            self.line += 1;
+
+            match next {
+                Ok(line) if line.trim().is_empty() => {
+                    if !record.is_empty() {
+                        return Some(Ok((start, record)));
+                    }
+                }
+                Ok(line) => {
+                    if record.is_empty() {
+                        start = self.line;
+                    }
+
+                    record.push(line);
+                }
+                Err(err) => {
+                    // a failing reader may keep failing, don't read past it
+                    self.failed = true;
+
+                    return Some(Err(line_io_error(self.line, &err)));
+                }
+            }
+        }
+    }
 }
 
 /// create an `io::Error`
 #[inline]
+#[must_use]
 pub fn io_error(error: &str) -> io::Error {
-    io::Error::new(ErrorKind::Other, error)
+    io::Error::other(error)
+}
+
+/// an `io::Error` of reading a line of input, keeping the kind of the original error
+#[must_use]
+pub fn line_io_error(line: usize, err: &io::Error) -> io::Error {
+    io::Error::new(err.kind(), format!("line {line}; {err}"))
 }
 
-// sums everything in iterator honoring errors
-#[allow(clippy::option_if_let_else)] // `map_or` requires `E: Error` to also implement `Copy`
-pub fn sum_everything<T, E>(mut items: impl Iterator<Item=Result<T, E>>) -> Result<T, E>
-    where T: Default + Copy + Add<Output=T>,
-          E: Error,
+/// sums everything in iterator honoring errors, the sum is checked for overflow;
+/// each item comes with the record of the day's input it's of, i.e. its [`Line`]
+///
+/// # Errors
+///
+/// the first error in the iterator, or an overflow naming the day and the record that overflows the sum
+pub fn sum_everything<K, T, E>(day: u8, mut items: impl Iterator<Item=Result<(K, T), E>>) -> Result<T, E>
+    where K: Display,
+          T: CheckedAdd + Zero,
+          E: From<AocError>,
 {
-    let sum_result = items
-        .try_fold(
-            T::default(),
-            |acc, nxt| match nxt {
-                Ok(nxt) => ControlFlow::Continue(acc + nxt),
-                Err(_) => ControlFlow::Break(nxt)
-            },
-        );
-
-    match sum_result {
-        ControlFlow::Continue(ok) => Ok(ok),
-        ControlFlow::Break(err) => err
+    items.try_fold(T::zero(), |sum, item| {
+        let (record, item) = item?;
+
+        sum.checked_add(&item).ok_or_else(|| AocError::overflow(format!("day {day}, {record}")).into())
+    })
+}
+
+/// A line of input a record is on, starting at 1
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Line(pub usize);
+
+impl Display for Line {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "line {}", self.0)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+
+    use super::{CleansedLines, Line, Records, sum_everything};
+
+    #[test]
+    fn cleansed_lines_report_io_errors_and_end() {
+        let mut lines = CleansedLines::new(&b"one\n\n\xff\ntwo\n"[..]);
+
+        assert_eq!(lines.next().map(Result::ok), Some(Some(String::from("one"))));
+
+        let err = lines.next().and_then(Result::err).expect("an invalid UTF-8 line");
+
+        assert!(err.to_string().starts_with("line 3; "), "unexpected error; {err}");
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(lines.next().is_none());
+    }
+
+    #[test]
+    fn records_split_on_blank_lines() {
+        let mut records = Records::new(&b"\none\ntwo\n\n  \nthree\n\n\xff\nfour\n"[..]);
+
+        assert_eq!(records.next().and_then(Result::ok), Some((2, vec![String::from("one"), String::from("two")])));
+        assert_eq!(records.next().and_then(Result::ok), Some((6, vec![String::from("three")])));
+
+        let err = records.next().and_then(Result::err).expect("an invalid UTF-8 line");
+
+        assert!(err.to_string().starts_with("line 8; "), "unexpected error; {err}");
+        assert!(records.next().is_none());
+        assert_eq!(Records::new(&b"one\ntwo"[..]).map(|record| record.map(|(_, lines)| lines.len()).ok()).collect::<Vec<_>>(), [Some(2)]);
+    }
+
+    #[test]
+    fn sum_naming_the_record_that_overflows() {
+        let items = |items: [u8; 3]| items.into_iter().zip(3..).map(|(item, line)| Ok::<_, AocError>((Line(line), item)));
+
+        assert_eq!(sum_everything(3, items([1, 2, 3])).ok(), Some(6));
+        assert!(matches!(sum_everything(3, items([200, 50, 6])), Err(AocError::Overflow(record)) if record == "day 3, line 5"));
+    }
+}
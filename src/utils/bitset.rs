@@ -0,0 +1,197 @@
+//! Sets of items of small alphabets, a bit of a mask for each item, i.e. the items of the day 3 rucksacks
+
+use std::fmt::Debug;
+use std::ops::{BitAnd, BitOr, Shl};
+
+/// A mask of bits, a bit for each item of an alphabet of up to `BITS` items
+pub trait Mask: Copy + Debug + Default + Eq + BitAnd<Output=Self> + BitOr<Output=Self> + Shl<usize, Output=Self> {
+    /// number of items the mask has a bit for
+    const BITS: usize;
+    /// the mask with only the first bit set
+    const ONE: Self;
+
+    /// number of set bits
+    fn ones(self) -> usize;
+
+    /// the mask with the lowest set bit cleared
+    #[must_use]
+    fn clear_lowest(self) -> Self;
+
+    /// index of the lowest set bit, `BITS` if there isn't one
+    fn lowest(self) -> usize;
+}
+
+macro_rules! impl_mask {
+    ($($mask:ty),*) => {$(
+        impl Mask for $mask {
+            const BITS: usize = <$mask>::BITS as usize;
+            const ONE: Self = 1;
+
+            fn ones(self) -> usize {
+                self.count_ones() as usize
+            }
+
+            fn clear_lowest(self) -> Self {
+                self & self.wrapping_sub(1)
+            }
+
+            fn lowest(self) -> usize {
+                self.trailing_zeros() as usize
+            }
+        }
+    )*};
+}
+
+impl_mask!(u64, u128);
+
+/// A set of items of an alphabet, the items are indices into the alphabet;
+/// a `u64` mask holds alphabets of up to 64 items, a `u128` mask up to 128 i.e. ascii
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct BitSet<M> {
+    mask: M,
+}
+
+impl<M> BitSet<M>
+    where M: Mask
+{
+    /// an empty set
+    #[must_use]
+    pub fn new() -> Self {
+        Self { mask: M::default() }
+    }
+
+    /// a set of items, `index` maps each item into the alphabet or fails
+    ///
+    /// # Errors
+    ///
+    /// the first error of mapping an item into the alphabet
+    ///
+    /// # Panics
+    ///
+    /// if an index is outside the alphabet of the mask
+    pub fn from_items<T, E>(items: impl IntoIterator<Item=T>, mut index: impl FnMut(T) -> Result<usize, E>) -> Result<Self, E> {
+        let mut set = Self::new();
+
+        for item in items {
+            set.insert(index(item)?);
+        }
+
+        Ok(set)
+    }
+
+    /// adds an item to the set, `false` if it was already in it
+    ///
+    /// # Panics
+    ///
+    /// if the index is outside the alphabet of the mask
+    pub fn insert(&mut self, index: usize) -> bool {
+        assert!(index < M::BITS, "item {index} is outside an alphabet of {} items", M::BITS);
+
+        let present = self.contains(index);
+
+        self.mask = self.mask | (M::ONE << index);
+
+        !present
+    }
+
+    /// the item is in the set
+    #[must_use]
+    pub fn contains(&self, index: usize) -> bool {
+        index < M::BITS && self.mask & (M::ONE << index) != M::default()
+    }
+
+    /// the items in both sets
+    #[must_use]
+    pub fn intersection(self, other: Self) -> Self {
+        Self { mask: self.mask & other.mask }
+    }
+
+    /// number of items in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.mask.ones()
+    }
+
+    /// the set has no items
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.mask == M::default()
+    }
+
+    /// the items of the set, in alphabet order
+    pub fn iter(&self) -> impl Iterator<Item=usize> {
+        let mut mask = self.mask;
+
+        std::iter::from_fn(move || {
+            if mask == M::default() {
+                return None;
+            }
+
+            let index = mask.lowest();
+
+            mask = mask.clear_lowest();
+
+            Some(index)
+        })
+    }
+}
+
+/// the items common to every set of items, `index` maps each item into the alphabet or fails;
+/// empty if there are no sets
+///
+/// # Errors
+///
+/// the first error of mapping an item into the alphabet
+///
+/// # Panics
+///
+/// if an index is outside the alphabet of the mask
+pub fn common_items<M, S, T, E>(sets: impl IntoIterator<Item=S>, mut index: impl FnMut(T) -> Result<usize, E>) -> Result<BitSet<M>, E>
+    where M: Mask,
+          S: IntoIterator<Item=T>
+{
+    let mut common: Option<BitSet<M>> = None;
+
+    for set in sets {
+        let set = BitSet::from_items(set, &mut index)?;
+
+        common = Some(common.map_or(set, |common| common.intersection(set)));
+    }
+
+    Ok(common.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BitSet, common_items};
+
+    fn ascii(item: char) -> Result<usize, String> {
+        item.is_ascii().then_some(item as usize).ok_or_else(|| format!("'{item}' is not ascii"))
+    }
+
+    #[test]
+    fn intersect_any_number_of_sets() {
+        let common = common_items::<u128, _, _, _>(["vJrwpWtwJgWr".chars(), "hcsFMMfFFhFpJr".chars()], ascii);
+        let common = common.expect("ascii items");
+
+        assert_eq!(common.iter().collect::<Vec<_>>(), [usize::from(b'J'), usize::from(b'p'), usize::from(b'r')]);
+        assert_eq!(common.len(), 3);
+
+        let common = common_items::<u128, _, _, _>(["abc".chars(), "bcd".chars(), "cde".chars()], ascii);
+
+        assert_eq!(common.map(|common| common.iter().collect::<Vec<_>>()), Ok(vec![usize::from(b'c')]));
+        assert!(common_items::<u64, std::str::Chars<'_>, _, _>([], ascii).is_ok_and(|common| common.is_empty()));
+        assert_eq!(common_items::<u128, _, _, _>(["ab".chars(), "bé".chars()], ascii), Err(String::from("'é' is not ascii")));
+    }
+
+    #[test]
+    fn insert_items_of_an_alphabet() {
+        let mut set = BitSet::<u64>::new();
+
+        assert!(set.insert(0));
+        assert!(set.insert(63));
+        assert!(!set.insert(63));
+        assert!(set.contains(63) && !set.contains(1) && !set.contains(64));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 63]);
+    }
+}
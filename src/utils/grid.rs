@@ -0,0 +1,303 @@
+//! A rectangular grid of cells, i.e. the day 8 forrest or the day 10 crt frame
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::io::Read;
+
+use crate::error::{AocError, ParseError};
+use crate::utils::CleansedLines;
+
+/// Directions across a grid, rows run top to bottom, columns left to right
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Direction {
+    /// up, towards the first row
+    North,
+    /// right, towards the last column
+    East,
+    /// down, towards the last row
+    South,
+    /// left, towards the first column
+    West,
+}
+
+impl Direction {
+    /// every direction, clockwise from north
+    pub const ALL: [Self; 4] = [Self::North, Self::East, Self::South, Self::West];
+
+    /// the location one step in this direction, `None` if it is left of or above the grid
+    #[must_use]
+    pub fn step(self, x: usize, y: usize) -> Option<(usize, usize)> {
+        Some(match self {
+            Self::North => (x, y.checked_sub(1)?),
+            Self::East => (x + 1, y),
+            Self::South => (x, y + 1),
+            Self::West => (x.checked_sub(1)?, y),
+        })
+    }
+}
+
+/// A grid of cells of a width and height, with bounds checked access;
+/// `x` is the column and `y` the row of a cell
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// a grid of a width and height with every cell the same
+    #[must_use]
+    pub fn filled(width: usize, height: usize, cell: T) -> Self
+        where T: Clone
+    {
+        Self {
+            cells: vec![cell; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// a grid from its cells row by row, `None` if they don't fill the rows of a width
+    #[must_use]
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        let height = cells.len() / width;
+
+        Some(Self { cells, width, height })
+    }
+
+    /// parses a grid from the non empty lines of a day's input, a cell for each character;
+    /// every row has to be as wide as the first
+    ///
+    /// # Errors
+    ///
+    /// fails if the input can't be read, a character isn't a cell or a row is too narrow or too wide
+    pub fn parse<R>(input: R, day: u8, mut cell: impl FnMut(char) -> Result<T, String>) -> Result<Self, AocError>
+        where R: Read
+    {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for line in CleansedLines::new(input).numbered() {
+            let (line, row) = line?;
+            let mut columns = 0;
+
+            for (idx, next) in row.chars().enumerate() {
+                cells.push(cell(next).map_err(|err| ParseError::new(idx + 1, err).in_line(day, line, &row))?);
+
+                columns += 1;
+            }
+
+            let expected = *width.get_or_insert(columns);
+
+            if columns != expected {
+                return Err(
+                    ParseError::new(columns.min(expected) + 1, format!("row is {columns} cells wide, expected {expected}"))
+                        .in_line(day, line, &row)
+                );
+            }
+
+            height += 1;
+        }
+
+        Ok(Self { cells, width: width.unwrap_or_default(), height })
+    }
+
+    /// number of columns
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    /// number of rows
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// the cell at a location, `None` if it is outside the grid
+    #[must_use]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index(x, y).map(|idx| &self.cells[idx])
+    }
+
+    /// the mutable cell at a location, `None` if it is outside the grid
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index(x, y).map(|idx| &mut self.cells[idx])
+    }
+
+    /// replaces the cell at a location, cells outside the grid are ignored;
+    /// returns the replaced cell
+    pub fn set(&mut self, x: usize, y: usize, cell: T) -> Option<T> {
+        self.get_mut(x, y).map(|current| std::mem::replace(current, cell))
+    }
+
+    /// every cell along with its location, row by row
+    pub fn cells(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+        let width = self.width.max(1);
+
+        self.cells.iter().enumerate().map(move |(idx, cell)| ((idx % width, idx / width), cell))
+    }
+
+    /// the rows of the grid, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// the cells of a row, left to right; empty if the row is outside the grid
+    pub fn row(&self, y: usize) -> impl Iterator<Item=&T> {
+        self.rows().nth(y).unwrap_or_default().iter()
+    }
+
+    /// the cells of a column, top to bottom; empty if the column is outside the grid
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        let rows = if x < self.width { self.height } else { 0 };
+
+        (0..rows).map(move |y| &self.cells[x + y * self.width])
+    }
+
+    /// the cells from a location, excluded, to the edge of the grid in a direction,
+    /// along with their locations
+    pub fn ray(&self, x: usize, y: usize, direction: Direction) -> impl Iterator<Item=((usize, usize), &T)> {
+        let mut current = Some((x, y));
+
+        std::iter::from_fn(move || {
+            let (x, y) = direction.step(current?.0, current?.1)?;
+            let cell = self.get(x, y)?;
+
+            current = Some((x, y));
+
+            Some(((x, y), cell))
+        })
+    }
+
+    /// the cells next to a location, north, east, south and west of it, that are inside the grid,
+    /// along with their locations
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item=((usize, usize), &T)> {
+        Direction::ALL.into_iter()
+            .filter_map(move |direction| {
+                let (x, y) = direction.step(x, y)?;
+
+                self.get(x, y).map(|cell| ((x, y), cell))
+            })
+    }
+
+    /// index of a cell, `None` if it is outside the grid
+    const fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(x + y * self.width)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> Grid<T>
+    where T: Clone + Default
+{
+    /// a grid of a width and height with default cells
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::filled(width, height, T::default())
+    }
+}
+
+/// displays the grid row by row, each cell as it displays
+impl<T> Display for Grid<T>
+    where T: Display
+{
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(fmt)?;
+            }
+
+            for cell in row {
+                write!(fmt, "{cell}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::AocError;
+
+    use super::{Direction, Grid};
+
+    fn digits(input: &str) -> Result<Grid<u32>, AocError> {
+        Grid::parse(input.as_bytes(), 8, |cell| cell.to_digit(10).ok_or_else(|| format!("'{cell}' is not a digit")))
+    }
+
+    #[test]
+    fn access_cells_within_bounds() {
+        let mut grid = digits("123\n456\n").expect("a grid of digits");
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.set(0, 1, 9), Some(4));
+        assert_eq!(grid.set(0, 2, 9), None);
+        assert_eq!(grid.row(1).copied().collect::<Vec<_>>(), [9, 5, 6]);
+        assert_eq!(grid.row(2).count(), 0);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(grid.to_string(), "123\n956");
+    }
+
+    #[test]
+    fn walk_rays_and_neighbors() {
+        let grid = digits("123\n456\n789").expect("a grid of digits");
+
+        let ray = |x, y, direction| grid.ray(x, y, direction).map(|(_, cell)| *cell).collect::<Vec<_>>();
+
+        assert_eq!(ray(1, 1, Direction::North), [2]);
+        assert_eq!(ray(0, 1, Direction::East), [5, 6]);
+        assert_eq!(ray(2, 0, Direction::South), [6, 9]);
+        assert!(ray(0, 0, Direction::West).is_empty());
+
+        let neighbors = grid.neighbors(0, 1).collect::<Vec<_>>();
+
+        assert_eq!(neighbors, [((0, 0), &1), ((1, 1), &5), ((0, 2), &7)]);
+        assert_eq!(grid.cells().nth(5), Some(((2, 1), &6)));
+    }
+
+    #[test]
+    fn locate_invalid_grids() {
+        let Err(AocError::Parse { line, column, .. }) = digits("123\n4x6") else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (2, 2));
+
+        let Err(AocError::Parse { line, column, .. }) = digits("123\n45") else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (2, 3));
+
+        // columns count characters, not bytes
+        let letters = Grid::parse("abc\néèx".as_bytes(), 8, |cell| if cell == 'x' { Err(String::from("an x")) } else { Ok(cell) });
+
+        let Err(AocError::Parse { line, column, .. }) = letters else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (2, 3));
+
+        let Err(AocError::Parse { line, column, .. }) = digits("1é3\n456") else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column), (1, 2));
+        assert_eq!(Grid::from_cells(2, vec![1, 2, 3]), None);
+        assert_eq!(Grid::from_cells(2, vec![1, 2, 3, 4]).map(|grid| grid.height()), Some(2));
+    }
+}
@@ -0,0 +1,256 @@
+//! Parsing toolkit, nom combinators for the usual shapes of puzzle input, which fail with
+//! what was expected where; i.e. a day's line parser is a few combinators and a [`parse_line`]
+
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, digit1, line_ending, multispace0, space0, space1};
+use nom::combinator::{all_consuming, map, map_res, opt, recognize};
+use nom::error::{ErrorKind, FromExternalError};
+use nom::IResult;
+use nom::multi::{many1, separated_list1};
+use nom::sequence::{pair, preceded, separated_pair, terminated, tuple};
+
+use crate::error::{AocError, ParseError};
+
+/// result of a toolkit parser
+pub type Parsed<'a, T> = IResult<&'a str, T, LineError<'a>>;
+
+/// An error of a toolkit parser, the input where it failed and what was expected there
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineError<'a> {
+    /// remaining input where parsing failed
+    pub input: &'a str,
+    /// what was expected, or why the input isn't valid
+    pub message: String,
+}
+
+impl<'a> nom::error::ParseError<&'a str> for LineError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        let message = match kind {
+            ErrorKind::Digit => String::from("expected digits"),
+            ErrorKind::Alpha => String::from("expected letters"),
+            ErrorKind::AlphaNumeric => String::from("expected letters or digits"),
+            ErrorKind::Space | ErrorKind::MultiSpace => String::from("expected whitespace"),
+            ErrorKind::CrLf => String::from("expected a line ending"),
+            ErrorKind::Eof => String::from("unexpected trailing input"),
+            kind => format!("expected {}", kind.description().to_lowercase()),
+        };
+
+        Self { input, message }
+    }
+
+    fn append(_input: &'a str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+/// a value that couldn't be converted, i.e. a number too large, fails with why
+impl<'a, E> FromExternalError<&'a str, E> for LineError<'a>
+    where E: Display
+{
+    fn from_external_error(input: &'a str, _kind: ErrorKind, err: E) -> Self {
+        Self { input, message: format!("'{input}' is not valid; {err}") }
+    }
+}
+
+/// parses all of a line, but trailing whitespace, failing with the column where and why it isn't valid
+///
+/// # Errors
+///
+/// fails if the parser fails or doesn't parse all of the line
+pub fn parse_line<'a, T>(line: &'a str, parser: impl FnMut(&'a str) -> Parsed<'a, T>) -> Result<T, ParseError> {
+    all_consuming(terminated(parser, space0))(line)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| match err {
+            nom::Err::Error(failed) | nom::Err::Failure(failed) =>
+                ParseError::at(line, failed.input, failed.message),
+            nom::Err::Incomplete(_) =>
+                ParseError::new(line.len() + 1, "incomplete line"),
+        })
+}
+
+/// parses all of a day's text of many lines, but trailing whitespace,
+/// failing with the line and column where and why it isn't valid
+///
+/// # Errors
+///
+/// fails if the parser fails or doesn't parse all of the text
+pub fn parse_text<'a, T>(text: &'a str, day: u8, parser: impl FnMut(&'a str) -> Parsed<'a, T>) -> Result<T, AocError> {
+    all_consuming(terminated(parser, multispace0))(text)
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| {
+            let (remaining, message) = match err {
+                nom::Err::Error(failed) | nom::Err::Failure(failed) => (failed.input, failed.message),
+                nom::Err::Incomplete(_) => (&text[text.len()..], String::from("incomplete text")),
+            };
+
+            // locate the line of the text the parser failed in
+            let offset = text.len() - remaining.len();
+            let start = text[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+            let end = text[offset..].find('\n').map_or(text.len(), |newline| offset + newline);
+            let line = text[..offset].matches('\n').count() + 1;
+            let snippet = text[start..end].trim_end_matches('\r');
+
+            ParseError::new(offset - start + 1, message).in_line(day, line, snippet)
+        })
+}
+
+/// fails with a message of what was expected, instead of the message of the parser;
+/// failures past a `cut` keep their own message
+pub fn expect<'a, T>(
+    message: impl Display,
+    mut parser: impl FnMut(&'a str) -> Parsed<'a, T>,
+) -> impl FnMut(&'a str) -> Parsed<'a, T> {
+    let message = message.to_string();
+
+    move |input| parser(input)
+        .map_err(|err| match err {
+            nom::Err::Error(_) => nom::Err::Error(LineError { input, message: message.clone() }),
+            failed => failed,
+        })
+}
+
+/// exactly some text, i.e. a keyword or a delimiter
+pub fn literal<'a>(text: &'static str) -> impl FnMut(&'a str) -> Parsed<'a, &'a str> {
+    expect(format!("expected `{text}`"), tag(text))
+}
+
+/// an unsigned integer, i.e. `42`
+///
+/// # Errors
+///
+/// fails if the input isn't digits or they don't fit the integer type
+pub fn unsigned<N>(input: &str) -> Parsed<'_, N>
+    where N: FromStr,
+          N::Err: Display
+{
+    map_res(digit1, str::parse)(input)
+}
+
+/// a signed integer, i.e. `-42` or `+42` or `42`
+///
+/// # Errors
+///
+/// fails if the input isn't an optionally signed run of digits or they don't fit the integer type
+pub fn signed<N>(input: &str) -> Parsed<'_, N>
+    where N: FromStr,
+          N::Err: Display
+{
+    map_res(recognize(pair(opt(alt((char('-'), char('+')))), digit1)), str::parse)(input)
+}
+
+/// two values separated by a delimiter, i.e. `2,4`; a whitespace delimiter separates the values
+/// by any amount of whitespace, any other delimiter can be surrounded by whitespace
+pub fn delimited_pair<'a, A, B>(
+    first: impl FnMut(&'a str) -> Parsed<'a, A>,
+    delimiter: &'static str,
+    second: impl FnMut(&'a str) -> Parsed<'a, B>,
+) -> impl FnMut(&'a str) -> Parsed<'a, (A, B)> {
+    let mut separator = literal(delimiter.trim());
+
+    separated_pair(
+        first,
+        move |input| if delimiter.trim().is_empty() {
+            space1(input)
+        } else {
+            tuple((space0, &mut separator, space0))(input).map(|(next, (_, separator, _))| (next, separator))
+        },
+        second,
+    )
+}
+
+/// an inclusive range of unsigned integers, i.e. `2-4`
+///
+/// # Errors
+///
+/// fails if the input isn't two unsigned integers separated by a `-`
+pub fn range<N>(input: &str) -> Parsed<'_, RangeInclusive<N>>
+    where N: FromStr,
+          N::Err: Display
+{
+    map(delimited_pair(unsigned, "-", unsigned), |(start, end)| start..=end)(input)
+}
+
+/// a keyword followed by whitespace and a value, i.e. `move 3`
+pub fn keyword<'a, T>(
+    word: &'static str,
+    value: impl FnMut(&'a str) -> Parsed<'a, T>,
+) -> impl FnMut(&'a str) -> Parsed<'a, T> {
+    preceded(tuple((space0, literal(word), space1)), value)
+}
+
+/// a keyword, a colon and a value, i.e. `Test: divisible by 23`; the line can be indented
+pub fn keyword_value<'a, T>(
+    word: &'static str,
+    value: impl FnMut(&'a str) -> Parsed<'a, T>,
+) -> impl FnMut(&'a str) -> Parsed<'a, T> {
+    preceded(tuple((space0, literal(word), literal(":"), space0)), value)
+}
+
+/// lines of values, each line parsed by the same parser
+pub fn lines<'a, T>(line: impl FnMut(&'a str) -> Parsed<'a, T>) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    separated_list1(line_ending, line)
+}
+
+/// records separated by blank lines, i.e. the calories carried by each elf
+pub fn records<'a, T>(record: impl FnMut(&'a str) -> Parsed<'a, T>) -> impl FnMut(&'a str) -> Parsed<'a, Vec<T>> {
+    separated_list1(pair(line_ending, many1(pair(space0, line_ending))), record)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::alpha1;
+    use nom::sequence::tuple;
+
+    use crate::error::AocError;
+
+    use super::{delimited_pair, keyword, keyword_value, lines, parse_line, parse_text, range, records, signed, unsigned};
+
+    #[test]
+    fn parse_lines() {
+        assert_eq!(parse_line("42 ", unsigned::<u8>), Ok(42));
+        assert_eq!(parse_line("-42", signed::<i32>), Ok(-42));
+        assert_eq!(parse_line("2-4,6-8", delimited_pair(range::<u8>, ",", range)), Ok((2..=4, 6..=8)));
+        assert_eq!(parse_line("A   Y", delimited_pair(alpha1, " ", alpha1)), Ok(("A", "Y")));
+        assert_eq!(parse_line("  Test: divisible by 23", keyword_value("Test", keyword("divisible", keyword("by", unsigned::<u32>)))), Ok(23));
+        assert_eq!(
+            parse_line("move 3 from 1 to 2", tuple((keyword("move", unsigned::<u8>), keyword("from", alpha1), keyword("to", alpha1))))
+                .map_err(|err| err.column),
+            Err(13)
+        );
+    }
+
+    #[test]
+    fn locate_invalid_lines() {
+        let failed = parse_line("2-4,6-x", delimited_pair(range::<u8>, ",", range::<u8>)).expect_err("not a range");
+
+        assert_eq!((failed.column, failed.message.as_str()), (7, "expected digits"));
+
+        let failed = parse_line("300", unsigned::<u8>).expect_err("too large");
+
+        assert_eq!(failed.column, 1);
+        assert!(failed.message.starts_with("'300' is not valid"), "unexpected message; {}", failed.message);
+
+        let failed = parse_line("1-2 3", range::<u8>).expect_err("trailing input");
+
+        assert_eq!((failed.column, failed.message.as_str()), (5, "unexpected trailing input"));
+        assert_eq!(parse_line("move 3", keyword("mode", unsigned::<u8>)).map_err(|err| err.message), Err(String::from("expected `mode`")));
+    }
+
+    #[test]
+    fn parse_records_of_lines() {
+        let text = "1000\n2000\n\n\n4000\n";
+
+        assert_eq!(parse_text(text, 1, records(lines(unsigned::<u32>))).ok(), Some(vec![vec![1000, 2000], vec![4000]]));
+
+        let Err(AocError::Parse { line, column, snippet, .. }) = parse_text("1000\n2x00\n\n3000", 1, records(lines(unsigned::<u32>))) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((line, column, snippet.as_str()), (2, 2, "2x00"));
+    }
+}
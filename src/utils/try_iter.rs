@@ -0,0 +1,101 @@
+//! Folds of iterators of results that end at the first error, i.e. the totals of the elves' calories
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use num_traits::{CheckedAdd, Zero};
+
+/// Folds of an iterator of results, each ends at and fails with the first error
+pub trait TryIterator<T, E>: Iterator<Item=Result<T, E>> + Sized {
+    /// sum of the items, failing with `overflow` of the position of the item, starting at 1,
+    /// when adding it overflows the sum
+    ///
+    /// # Errors
+    ///
+    /// the first error of the iterator, or the overflow
+    fn try_checked_sum(self, mut overflow: impl FnMut(usize) -> E) -> Result<T, E>
+        where T: CheckedAdd + Zero
+    {
+        self.zip(1..)
+            .try_fold(T::zero(), |sum, (item, position)| sum.checked_add(&item?).ok_or_else(|| overflow(position)))
+    }
+
+    /// the largest item, `None` if there are no items
+    ///
+    /// # Errors
+    ///
+    /// the first error of the iterator
+    fn try_max(mut self) -> Result<Option<T>, E>
+        where T: Ord
+    {
+        self.try_fold(None, |max, item| Ok(max.max(Some(item?))))
+    }
+
+    /// the `k` largest items, largest first; holds no more than `k` items at a time
+    ///
+    /// # Errors
+    ///
+    /// the first error of the iterator
+    fn try_top_k(mut self, k: usize) -> Result<Vec<T>, E>
+        where T: Ord
+    {
+        // grows with the items, `k` may be far more than there are
+        let mut top = BinaryHeap::new();
+
+        self.try_for_each(|item| {
+            top.push(Reverse(item?));
+
+            if top.len() > k {
+                top.pop();
+            }
+
+            Ok(())
+        })?;
+
+        Ok(top.into_sorted_vec().into_iter().map(|Reverse(item)| item).collect())
+    }
+
+    /// number of items that match a predicate
+    ///
+    /// # Errors
+    ///
+    /// the first error of the iterator
+    fn try_count_where(mut self, mut predicate: impl FnMut(&T) -> bool) -> Result<usize, E> {
+        self.try_fold(0, |count, item| Ok(count + usize::from(predicate(&item?))))
+    }
+}
+
+impl<I, T, E> TryIterator<T, E> for I
+    where I: Iterator<Item=Result<T, E>>
+{}
+
+#[cfg(test)]
+mod tests {
+    use super::TryIterator;
+
+    fn items<'a>(items: &'a [Result<u32, &'static str>]) -> impl Iterator<Item=Result<u32, &'static str>> + 'a {
+        items.iter().copied()
+    }
+
+    #[test]
+    fn fold_until_the_first_error() {
+        let valid = [Ok(3), Ok(1), Ok(4), Ok(1), Ok(5)];
+        let invalid = [Ok(3), Err("first"), Ok(4), Err("second")];
+
+        assert_eq!(items(&valid).try_max(), Ok(Some(5)));
+        assert_eq!(items(&[]).try_max(), Ok(None));
+        assert_eq!(items(&valid).try_top_k(3), Ok(vec![5, 4, 3]));
+        assert_eq!(items(&valid[..2]).try_top_k(3), Ok(vec![3, 1]));
+        assert_eq!(items(&valid[..2]).try_top_k(usize::MAX), Ok(vec![3, 1]));
+        assert_eq!(items(&valid).try_count_where(|item| *item == 1), Ok(2));
+
+        assert_eq!(items(&valid).try_checked_sum(|_| "overflow"), Ok(14));
+        assert_eq!(items(&[Ok(u32::MAX), Ok(0), Ok(1)]).try_checked_sum(|_| "overflow"), Err("overflow"));
+        assert_eq!(items(&[Ok(u32::MAX), Ok(1)]).try_checked_sum(|position| if position == 2 { "second" } else { "other" }), Err("second"));
+
+        assert_eq!(items(&invalid).try_checked_sum(|_| "overflow"), Err("first"));
+        assert_eq!(items(&invalid).try_max(), Err("first"));
+        assert_eq!(items(&invalid).try_top_k(1), Err("first"));
+        assert_eq!(items(&invalid).try_count_where(|_| true), Err("first"));
+    }
+}
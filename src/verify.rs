@@ -0,0 +1,150 @@
+//! Verification of puzzle answers against expected results
+
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// file of expected puzzle answers, as `label: answer` lines
+pub const EXPECTED_RESULTS: &str = "aoc-2022-rs-expected-results.txt";
+/// expected answer of a puzzle that isn't solved yet, which is not verified
+pub const PENDING: &str = "pending";
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// A difference between expected and actual answers
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Difference {
+    /// puzzle answered differently than expected
+    Mismatch {
+        /// label of the puzzle
+        label: String,
+        /// expected answer
+        expected: String,
+        /// actual answer
+        actual: String,
+    },
+    /// puzzle expected but not answered
+    Missing {
+        /// label of the puzzle
+        label: String,
+        /// expected answer
+        expected: String,
+    },
+    /// puzzle answered but not expected
+    Extra {
+        /// label of the puzzle
+        label: String,
+        /// actual answer
+        actual: String,
+    },
+}
+
+impl Difference {
+    /// renders the difference as diff lines, optionally colored
+    #[must_use]
+    pub fn render(&self, colored: bool) -> String {
+        let (removed, added, reset) = if colored { (RED, GREEN, RESET) } else { ("", "", "") };
+
+        match self {
+            Self::Mismatch { label, expected, actual } =>
+                format!("{removed}- {label}: {expected}{reset}\n{added}+ {label}: {actual}{reset}"),
+            Self::Missing { label, expected } =>
+                format!("{removed}- {label}: {expected}{reset} (missing)"),
+            Self::Extra { label, actual } =>
+                format!("{added}+ {label}: {actual}{reset} (extra)"),
+        }
+    }
+}
+
+impl Display for Difference {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{}", self.render(false))
+    }
+}
+
+/// parses expected results of `label: answer` lines, skipping empty lines
+///
+/// # Errors
+///
+/// fails with the first line that isn't a `label: answer`
+pub fn parse_expected(expected: &str) -> Result<Vec<(String, String)>, String> {
+    expected.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| line
+            .split_once(':')
+            .map(|(label, answer)| (label.trim().to_string(), answer.trim().to_string()))
+            .ok_or_else(|| format!("line {} '{line}' of expected results is not a `label: answer`", idx + 1))
+        )
+        .collect()
+}
+
+/// labels of the puzzles whose answers are pending, in expected order
+#[must_use]
+pub fn pending(expected: &[(String, String)]) -> Vec<&str> {
+    expected.iter()
+        .filter(|(_, answer)| answer == PENDING)
+        .map(|(label, _)| label.as_str())
+        .collect()
+}
+
+/// compares actual answers with expected answers, line by line in expected order,
+/// followed by any extra answers in actual order; pending answers are not compared
+#[must_use]
+pub fn compare(expected: &[(String, String)], actual: &[(String, String)]) -> Vec<Difference> {
+    let find = |answers: &[(String, String)], label: &str| answers.iter()
+        .find(|(answered, _)| answered == label)
+        .map(|(_, answer)| answer.clone());
+
+    let differences = expected.iter()
+        .filter(|(_, expected)| expected != PENDING)
+        .filter_map(|(label, expected)| match find(actual, label) {
+            None => Some(Difference::Missing { label: label.clone(), expected: expected.clone() }),
+            Some(actual) if actual != *expected =>
+                Some(Difference::Mismatch { label: label.clone(), expected: expected.clone(), actual }),
+            Some(_) => None,
+        });
+
+    let extras = actual.iter()
+        .filter(|(label, _)| find(expected, label).is_none())
+        .map(|(label, actual)| Difference::Extra { label: label.clone(), actual: actual.clone() });
+
+    differences.chain(extras).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare, Difference, parse_expected, pending};
+
+    #[test]
+    fn compare_answers() {
+        let expected = parse_expected("a: 1\nb: 2\n\nc: 3:4\n").expect("valid expected results");
+        let actual = parse_expected("a: 1\nc: 3:5\nd: 4").expect("valid actual results");
+
+        let differences = compare(&expected, &actual);
+
+        assert_eq!(differences, vec![
+            Difference::Missing { label: "b".into(), expected: "2".into() },
+            Difference::Mismatch { label: "c".into(), expected: "3:4".into(), actual: "3:5".into() },
+            Difference::Extra { label: "d".into(), actual: "4".into() },
+        ]);
+
+        assert_eq!(differences[1].to_string(), "- c: 3:4\n+ c: 3:5");
+        assert!(compare(&expected, &expected).is_empty());
+    }
+
+    #[test]
+    fn leave_pending_answers_unverified() {
+        let expected = parse_expected("a: 1\nb: pending\nc: pending").expect("valid expected results");
+        let actual = parse_expected("a: 1\nb: error; not solved yet").expect("valid actual results");
+
+        assert!(compare(&expected, &actual).is_empty());
+        assert_eq!(pending(&expected), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn reject_malformed_expected_results() {
+        assert!(parse_expected("a: 1\nno answer").is_err());
+    }
+}